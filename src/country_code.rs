@@ -13,7 +13,7 @@ use serde::{Deserialize, Serialize};
 /// let usa_code = CountryCode::USA;
 /// assert_eq!(usa_code.to_string(), "+1");
 /// ```
-#[derive(Debug, PartialEq, Serialize, Deserialize, Display)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Display)]
 pub enum CountryCode {
     #[display(fmt = "+1")]
     USA,
@@ -391,4 +391,217 @@ pub enum CountryCode {
     KGZ, // Kyrgyzstan
     #[display(fmt = "+998")]
     UZB, // Uzbekistan
+    #[display(fmt = "+1")]
+    CAN, // Canada
+    #[display(fmt = "+7")]
+    KAZ, // Kazakhstan
+}
+
+impl CountryCode {
+    /// Every supported country code, in declaration order.
+    pub const ALL: [CountryCode; 190] = [
+        CountryCode::USA,
+        CountryCode::UK,
+        CountryCode::IND,
+        CountryCode::INA,
+        CountryCode::CHN,
+        CountryCode::JPN,
+        CountryCode::KOR,
+        CountryCode::DEU,
+        CountryCode::RUS,
+        CountryCode::FRA,
+        CountryCode::ITA,
+        CountryCode::ESP,
+        CountryCode::AUS,
+        CountryCode::SGP,
+        CountryCode::ARG,
+        CountryCode::BRA,
+        CountryCode::CHL,
+        CountryCode::COL,
+        CountryCode::VEN,
+        CountryCode::MYS,
+        CountryCode::PHL,
+        CountryCode::NZL,
+        CountryCode::THA,
+        CountryCode::VNM,
+        CountryCode::TUR,
+        CountryCode::PAK,
+        CountryCode::AFG,
+        CountryCode::LKA,
+        CountryCode::MMR,
+        CountryCode::IRN,
+        CountryCode::MAR,
+        CountryCode::DZA,
+        CountryCode::TUN,
+        CountryCode::LBY,
+        CountryCode::GMB,
+        CountryCode::SEN,
+        CountryCode::MRT,
+        CountryCode::MLI,
+        CountryCode::GIN,
+        CountryCode::CIV,
+        CountryCode::BFA,
+        CountryCode::NER,
+        CountryCode::TGO,
+        CountryCode::BEN,
+        CountryCode::MUS,
+        CountryCode::LBR,
+        CountryCode::SLE,
+        CountryCode::GHA,
+        CountryCode::NGA,
+        CountryCode::TCD,
+        CountryCode::CAF,
+        CountryCode::CMR,
+        CountryCode::CPV,
+        CountryCode::STP,
+        CountryCode::GNQ,
+        CountryCode::GAB,
+        CountryCode::COG,
+        CountryCode::COD,
+        CountryCode::AGO,
+        CountryCode::GNB,
+        CountryCode::IOT,
+        CountryCode::SHN,
+        CountryCode::SYC,
+        CountryCode::SDN,
+        CountryCode::RWA,
+        CountryCode::ETH,
+        CountryCode::SOM,
+        CountryCode::DJI,
+        CountryCode::KEN,
+        CountryCode::TZA,
+        CountryCode::UGA,
+        CountryCode::BDI,
+        CountryCode::MOZ,
+        CountryCode::ZMB,
+        CountryCode::MDG,
+        CountryCode::REU,
+        CountryCode::ZWE,
+        CountryCode::NAM,
+        CountryCode::MWI,
+        CountryCode::LSO,
+        CountryCode::BWA,
+        CountryCode::SWZ,
+        CountryCode::COM,
+        CountryCode::ERI,
+        CountryCode::ABW,
+        CountryCode::FRO,
+        CountryCode::GRL,
+        CountryCode::GIB,
+        CountryCode::PRT,
+        CountryCode::LUX,
+        CountryCode::IRL,
+        CountryCode::ISL,
+        CountryCode::ALB,
+        CountryCode::MLT,
+        CountryCode::CYP,
+        CountryCode::FIN,
+        CountryCode::BGR,
+        CountryCode::LTU,
+        CountryCode::LVA,
+        CountryCode::EST,
+        CountryCode::MDA,
+        CountryCode::ARM,
+        CountryCode::BLR,
+        CountryCode::AND,
+        CountryCode::MCO,
+        CountryCode::SMR,
+        CountryCode::VAT,
+        CountryCode::UKR,
+        CountryCode::SRB,
+        CountryCode::MNE,
+        CountryCode::HRV,
+        CountryCode::SVN,
+        CountryCode::BIH,
+        CountryCode::MKD,
+        CountryCode::CZE,
+        CountryCode::SVK,
+        CountryCode::LIE,
+        CountryCode::FLK,
+        CountryCode::BLZ,
+        CountryCode::GTM,
+        CountryCode::SLV,
+        CountryCode::HND,
+        CountryCode::NIC,
+        CountryCode::CRI,
+        CountryCode::PAN,
+        CountryCode::SPM,
+        CountryCode::HTI,
+        CountryCode::GLP,
+        CountryCode::BOL,
+        CountryCode::GUY,
+        CountryCode::ECU,
+        CountryCode::MYT,
+        CountryCode::PRY,
+        CountryCode::MTQ,
+        CountryCode::SUR,
+        CountryCode::URY,
+        CountryCode::ANT,
+        CountryCode::TLS,
+        CountryCode::ATA,
+        CountryCode::BRN,
+        CountryCode::NRU,
+        CountryCode::PNG,
+        CountryCode::TON,
+        CountryCode::SLB,
+        CountryCode::VUT,
+        CountryCode::FJI,
+        CountryCode::PLW,
+        CountryCode::WLF,
+        CountryCode::COK,
+        CountryCode::NIU,
+        CountryCode::WSM,
+        CountryCode::KIR,
+        CountryCode::NCL,
+        CountryCode::TUV,
+        CountryCode::PYF,
+        CountryCode::TKL,
+        CountryCode::FSM,
+        CountryCode::MHL,
+        CountryCode::PRK,
+        CountryCode::HKG,
+        CountryCode::MAC,
+        CountryCode::KHM,
+        CountryCode::LAO,
+        CountryCode::BGD,
+        CountryCode::TWN,
+        CountryCode::MDV,
+        CountryCode::LBN,
+        CountryCode::JOR,
+        CountryCode::SYR,
+        CountryCode::IRQ,
+        CountryCode::KWT,
+        CountryCode::SAU,
+        CountryCode::YEM,
+        CountryCode::OMN,
+        CountryCode::PSE,
+        CountryCode::ARE,
+        CountryCode::ISR,
+        CountryCode::BHR,
+        CountryCode::QAT,
+        CountryCode::BTN,
+        CountryCode::MNG,
+        CountryCode::NPL,
+        CountryCode::TJK,
+        CountryCode::TKM,
+        CountryCode::AZE,
+        CountryCode::GEO,
+        CountryCode::KGZ,
+        CountryCode::UZB,
+        CountryCode::CAN,
+        CountryCode::KAZ,
+    ];
+
+    /// Returns the calling code digits without the leading `+`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use custom_type::CountryCode;
+    ///
+    /// assert_eq!(CountryCode::INA.calling_code(), "62");
+    /// ```
+    pub fn calling_code(&self) -> String {
+        self.to_string().trim_start_matches('+').to_string()
+    }
 }
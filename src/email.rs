@@ -1,8 +1,29 @@
 use crate::error::TypeError;
-use regex::Regex;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use std::fmt;
 
+mod canonical;
+mod classify;
+mod disposable;
+#[cfg(feature = "dns")]
+mod dns;
+mod mailbox;
+mod parser;
+mod policy;
+mod suggest;
+
+pub use canonical::{Canonicalization, Canonicalize};
+pub use classify::{FreeProviders, RoleAccounts};
+pub use disposable::DisposableDomains;
+#[cfg(feature = "hickory")]
+pub use dns::HickoryResolver;
+#[cfg(feature = "dns")]
+pub use dns::{Deliverability, DnsFuture, DnsResolver, MxRecord};
+pub use mailbox::{Address, AddressList, Group, Mailbox};
+pub use parser::EmailMode;
+pub use policy::EmailPolicy;
+pub use suggest::DomainSuggester;
+
 /// This crate provides a simple and efficient way to parse and validate email addresses.
 ///
 /// # Example
@@ -16,12 +37,18 @@ use std::fmt;
 ///
 /// # Features
 ///
-/// - Parse and validate email addresses using a regular expression.
-/// - Normalize email addresses to lowercase.
+/// - Parse and validate email addresses.
+/// - Preserve addresses as typed, with canonical forms for uniqueness checks.
+/// - Internationalized addresses: UTF-8 local parts (RFC 6531) and IDNA domains.
+/// - Detect or reject disposable email domains.
+/// - Classify role accounts and free webmail providers.
+/// - Suggest corrections for mistyped domains ("did you mean gmail.com?").
+/// - Parse mailboxes with display names and RFC 5322 address lists.
+/// - Check that a domain can receive mail through its MX records (`dns` feature).
 /// - Custom error type for handling invalid email addresses.
 /// ### Parse String To Valid Email
 /// Call the `parse()` method to parse `impl ToString` into a valid email.
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
 pub struct Email(String);
 
 impl fmt::Display for Email {
@@ -30,8 +57,20 @@ impl fmt::Display for Email {
     }
 }
 
+impl<'de> Deserialize<'de> for Email {
+    /// Deserializes a string and validates it with [`Email::parse`].
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let email = String::deserialize(deserializer)?;
+        Self::parse(email).map_err(serde::de::Error::custom)
+    }
+}
+
 impl Email {
-    /// Parses a given string into a valid email address.
+    /// Parses a given string into a valid email address, following the
+    /// RFC 5321 grammar ([`EmailMode::Rfc`]).
     ///
     /// # Arguments
     ///
@@ -40,7 +79,7 @@ impl Email {
     /// # Returns
     ///
     /// * `Ok(Self)` if the email is valid.
-    /// * `Err(TypeError::ParseError)` if the email is invalid.
+    /// * `Err(TypeError::Email)` if the email is invalid.
     ///
     /// # Examples
     ///
@@ -54,22 +93,325 @@ impl Email {
     /// assert!(invalid_email.is_err());
     /// ```
     pub fn parse(email: impl ToString) -> Result<Self, TypeError> {
-        // Normalize email
-        let email = email.to_string().to_lowercase();
-
-        // Email pattern on regular expression
-        let email_regex =
-            Regex::new(r"(?i)^[a-zA-Z0-9.!#$%&'*+/=?^_`{|}~]+@[a-zA-Z0-9-]+(?:\.[a-zA-Z0-9-]+)*$")
-                .unwrap();
-
-        // Validate email using regular expression
-        if email_regex.is_match(&email) {
-            Ok(Self(email))
-        } else {
-            Err(TypeError::ParseError(
-                "unable to parse email, invalid email.".to_string(),
-            ))
-        }
+        Self::parse_with(EmailMode::Rfc, email)
+    }
+
+    /// Parses a given string into a valid email address, following the
+    /// grammar of `mode`.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - How strictly to follow the address grammar.
+    /// * `email` - A string slice that holds the email address to be parsed.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` if the email is valid in `mode`.
+    /// * `Err(TypeError::Email)` if the email is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use custom_type::{Email, EmailMode};
+    ///
+    /// let email = Email::parse_with(EmailMode::Practical, "example@example.com");
+    /// assert!(email.is_ok());
+    ///
+    /// let invalid_email = Email::parse_with(EmailMode::Practical, "example@localhost");
+    /// assert!(invalid_email.is_err());
+    /// ```
+    pub fn parse_with(mode: EmailMode, email: impl ToString) -> Result<Self, TypeError> {
+        Self::parse_with_policy(&EmailPolicy::new().mode(mode), email)
+    }
+
+    /// Parses a given string into a valid email address satisfying `policy`.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - The grammar and checks the address must pass.
+    /// * `email` - A string slice that holds the email address to be parsed.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` if the email satisfies the policy.
+    /// * `Err(TypeError::Email)` with the first failed check otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use custom_type::{Email, EmailPolicy};
+    ///
+    /// let policy = EmailPolicy::new().reject_disposable();
+    /// assert!(Email::parse_with_policy(&policy, "jane@example.com").is_ok());
+    /// assert!(Email::parse_with_policy(&policy, "jane@yopmail.com").is_err());
+    /// ```
+    pub fn parse_with_policy(
+        policy: &EmailPolicy,
+        email: impl ToString,
+    ) -> Result<Self, TypeError> {
+        let email = email.to_string();
+        policy.check(&email)?;
+        Ok(Self(email))
+    }
+
+    /// Returns the address exactly as it was typed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use custom_type::Email;
+    ///
+    /// let email = Email::parse("Jane.Doe@Example.com").unwrap();
+    /// assert_eq!(email.as_str(), "Jane.Doe@Example.com");
+    /// ```
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the canonical form of the address for uniqueness checks, using
+    /// [`Canonicalization::DomainOnly`]: the domain is lowercased and the
+    /// local part, which is case-sensitive per RFC 5321, is kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use custom_type::Email;
+    ///
+    /// let email = Email::parse("Jane.Doe@Example.COM").unwrap();
+    /// assert_eq!(email.canonical(), "Jane.Doe@example.com");
+    /// ```
+    pub fn canonical(&self) -> String {
+        self.canonical_with(&Canonicalization::default())
+    }
+
+    /// Returns the canonical form of the address under `strategy`.
+    ///
+    /// # Arguments
+    ///
+    /// * `strategy` - A [`Canonicalization`] or a custom [`Canonicalize`] implementation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use custom_type::{Canonicalization, Email};
+    ///
+    /// let a = Email::parse("J.Doe+shop@googlemail.com").unwrap();
+    /// let b = Email::parse("jdoe@Gmail.com").unwrap();
+    /// assert_ne!(a.canonical(), b.canonical());
+    /// assert_eq!(
+    ///     a.canonical_with(&Canonicalization::ProviderAware),
+    ///     b.canonical_with(&Canonicalization::ProviderAware)
+    /// );
+    /// ```
+    pub fn canonical_with(&self, strategy: &dyn Canonicalize) -> String {
+        strategy.canonicalize(self.local_part(), &parser::to_unicode(self.domain()))
+    }
+
+    /// Returns the part before the `@`, including the quotes of a quoted local part.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use custom_type::Email;
+    ///
+    /// let email = Email::parse("\"john doe\"@example.com").unwrap();
+    /// assert_eq!(email.local_part(), "\"john doe\"");
+    /// ```
+    pub fn local_part(&self) -> &str {
+        self.split().0
+    }
+
+    /// Returns the part after the `@`, including the brackets of an address literal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use custom_type::Email;
+    ///
+    /// let email = Email::parse("user@[192.0.2.1]").unwrap();
+    /// assert_eq!(email.domain(), "[192.0.2.1]");
+    /// ```
+    pub fn domain(&self) -> &str {
+        self.split().1
+    }
+
+    /// Returns the domain in its lowercase ASCII-compatible form, with
+    /// internationalized labels encoded as punycode (`xn--`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use custom_type::Email;
+    ///
+    /// let email = Email::parse("josé@exämple.de").unwrap();
+    /// assert_eq!(email.domain(), "exämple.de");
+    /// assert_eq!(email.domain_ascii(), "xn--exmple-cua.de");
+    /// ```
+    pub fn domain_ascii(&self) -> String {
+        parser::to_ascii(self.domain())
+    }
+
+    /// Returns the address with its domain in ASCII-compatible form, or
+    /// `None` if the local part is not ASCII and the address can only be
+    /// delivered with SMTPUTF8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use custom_type::Email;
+    ///
+    /// let email = Email::parse("user@exämple.de").unwrap();
+    /// assert_eq!(email.to_ascii().as_deref(), Some("user@xn--exmple-cua.de"));
+    /// assert_eq!(Email::parse("josé@example.com").unwrap().to_ascii(), None);
+    /// ```
+    pub fn to_ascii(&self) -> Option<String> {
+        self.local_part()
+            .is_ascii()
+            .then(|| format!("{}@{}", self.local_part(), self.domain_ascii()))
+    }
+
+    /// Returns `true` if delivering to this address needs the SMTPUTF8
+    /// extension (RFC 6531), i.e. if its local part is not ASCII. An
+    /// internationalized domain alone does not, since it can be sent as punycode.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use custom_type::Email;
+    ///
+    /// assert!(Email::parse("用户@例子.广告").unwrap().requires_smtputf8());
+    /// assert!(!Email::parse("user@例子.广告").unwrap().requires_smtputf8());
+    /// ```
+    pub fn requires_smtputf8(&self) -> bool {
+        !self.local_part().is_ascii()
+    }
+
+    /// Returns `true` if the domain, or a parent domain, belongs to a
+    /// disposable email service in the list built into the crate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use custom_type::Email;
+    ///
+    /// assert!(Email::parse("jane@mailinator.com").unwrap().is_disposable());
+    /// assert!(Email::parse("jane@inbox.Mailinator.com").unwrap().is_disposable());
+    /// assert!(!Email::parse("jane@example.com").unwrap().is_disposable());
+    /// ```
+    pub fn is_disposable(&self) -> bool {
+        self.is_disposable_in(disposable::builtin())
+    }
+
+    /// Returns `true` if the domain matches `domains`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use custom_type::{DisposableDomains, Email};
+    ///
+    /// let mut domains = DisposableDomains::embedded();
+    /// domains.allow("mailinator.com");
+    /// assert!(!Email::parse("jane@mailinator.com").unwrap().is_disposable_in(&domains));
+    /// ```
+    pub fn is_disposable_in(&self, domains: &DisposableDomains) -> bool {
+        domains.contains(self.domain())
+    }
+
+    /// Returns `true` if the local part names a role or team, such as
+    /// `admin`, `noreply` or `support`, in the list built into the crate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use custom_type::Email;
+    ///
+    /// assert!(Email::parse("NoReply@example.com").unwrap().is_role_account());
+    /// assert!(Email::parse("support+billing@example.com").unwrap().is_role_account());
+    /// assert!(!Email::parse("jane@example.com").unwrap().is_role_account());
+    /// ```
+    pub fn is_role_account(&self) -> bool {
+        self.is_role_account_in(classify::builtin_roles())
+    }
+
+    /// Returns `true` if the local part matches `roles`.
+    pub fn is_role_account_in(&self, roles: &RoleAccounts) -> bool {
+        roles.contains(self.local_part())
+    }
+
+    /// Returns `true` if the domain belongs to a free webmail provider, such
+    /// as `gmail.com` or `outlook.com`, in the list built into the crate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use custom_type::Email;
+    ///
+    /// assert!(Email::parse("jane@Gmail.com").unwrap().is_free_provider());
+    /// assert!(!Email::parse("jane@example.com").unwrap().is_free_provider());
+    /// ```
+    pub fn is_free_provider(&self) -> bool {
+        self.is_free_provider_in(classify::builtin_providers())
+    }
+
+    /// Returns `true` if the domain matches `providers`.
+    pub fn is_free_provider_in(&self, providers: &FreeProviders) -> bool {
+        providers.contains(self.domain())
+    }
+
+    /// Suggests a corrected address when the domain looks like a typo of a
+    /// popular mail domain or top-level domain. The local part is kept as
+    /// typed.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Email)` with the corrected domain.
+    /// * `None` if the domain looks right or nothing is close enough.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use custom_type::Email;
+    ///
+    /// let email = Email::parse("Jane@gmial.com").unwrap();
+    /// assert_eq!(email.suggest_correction().unwrap().as_str(), "Jane@gmail.com");
+    ///
+    /// let email = Email::parse("jane@yaho.com").unwrap();
+    /// assert_eq!(email.suggest_correction().unwrap().as_str(), "jane@yahoo.com");
+    ///
+    /// assert!(Email::parse("jane@example.com").unwrap().suggest_correction().is_none());
+    /// ```
+    pub fn suggest_correction(&self) -> Option<Email> {
+        self.suggest_correction_with(suggest::builtin())
+    }
+
+    /// Suggests a corrected address using `suggester`'s domain lists and
+    /// thresholds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use custom_type::{DomainSuggester, Email};
+    ///
+    /// let suggester = DomainSuggester::new().domains(["example-corp.com"]);
+    /// let email = Email::parse("jane@exmaple-corp.com").unwrap();
+    /// assert_eq!(
+    ///     email.suggest_correction_with(&suggester).unwrap().as_str(),
+    ///     "jane@example-corp.com"
+    /// );
+    /// ```
+    pub fn suggest_correction_with(&self, suggester: &DomainSuggester) -> Option<Email> {
+        let domain = suggester.suggest(self.domain())?;
+        let email = format!("{}@{}", self.local_part(), domain);
+        parser::validate(&email, EmailMode::Rfc).ok()?;
+        Some(Self(email))
+    }
+
+    /// Splits the address at its last `@`; a quoted local part may contain
+    /// `@` but a domain never does.
+    fn split(&self) -> (&str, &str) {
+        self.0
+            .rsplit_once('@')
+            .expect("a parsed email contains '@'")
     }
 }
 
@@ -79,6 +421,7 @@ impl Email {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::EmailError;
 
     #[test]
     fn test_valid_emails() {
@@ -100,39 +443,90 @@ mod tests {
     fn test_invalid_emails() {
         assert_eq!(
             Email::parse("plainaddress"),
-            Err(TypeError::ParseError(
-                "unable to parse email, invalid email.".to_string()
-            ))
+            Err(TypeError::Email(EmailError::MissingAt))
         );
         assert_eq!(
             Email::parse("@missingusername.com"),
-            Err(TypeError::ParseError(
-                "unable to parse email, invalid email.".to_string()
-            ))
+            Err(TypeError::Email(EmailError::EmptyLocalPart))
         );
         assert_eq!(
             Email::parse("username@.com"),
-            Err(TypeError::ParseError(
-                "unable to parse email, invalid email.".to_string()
-            ))
+            Err(TypeError::Email(EmailError::InvalidDomain { position: 9 }))
         );
         assert_eq!(
             Email::parse("username@.com."),
-            Err(TypeError::ParseError(
-                "unable to parse email, invalid email.".to_string()
-            ))
+            Err(TypeError::Email(EmailError::InvalidDomain { position: 9 }))
         );
         assert_eq!(
             Email::parse("username@-ex@ample.com"),
-            Err(TypeError::ParseError(
-                "unable to parse email, invalid email.".to_string()
-            ))
+            Err(TypeError::Email(EmailError::InvalidDomain { position: 9 }))
+        );
+        assert_eq!(
+            Email::parse("username@ex@ample.com"),
+            Err(TypeError::Email(EmailError::InvalidDomain { position: 11 }))
         );
         assert_eq!(
             Email::parse("username@example..com"),
-            Err(TypeError::ParseError(
-                "unable to parse email, invalid email.".to_string()
-            ))
+            Err(TypeError::Email(EmailError::InvalidDomain { position: 17 }))
+        );
+        assert_eq!(
+            Email::parse("user name@example.com"),
+            Err(TypeError::Email(EmailError::InvalidLocalPart {
+                position: 4
+            }))
+        );
+        assert_eq!(
+            Email::parse("username@"),
+            Err(TypeError::Email(EmailError::EmptyDomain))
+        );
+    }
+    #[test]
+    fn test_accessors() {
+        let email = Email::parse("user.name+tag@example.co.id").unwrap();
+        assert_eq!(email.local_part(), "user.name+tag");
+        assert_eq!(email.domain(), "example.co.id");
+
+        let email = Email::parse("\"a@b\"@example.com").unwrap();
+        assert_eq!(email.local_part(), "\"a@b\"");
+        assert_eq!(email.domain(), "example.com");
+    }
+
+    #[test]
+    fn test_case_is_preserved() {
+        let email = Email::parse("John.Smith@Example.COM").unwrap();
+        assert_eq!(email.to_string(), "John.Smith@Example.COM");
+        assert_eq!(email.local_part(), "John.Smith");
+        assert_eq!(email.domain(), "Example.COM");
+        assert_eq!(email.domain_ascii(), "example.com");
+        assert_eq!(email.canonical(), "John.Smith@example.com");
+        assert_eq!(
+            email.canonical_with(&Canonicalization::Lowercase),
+            "john.smith@example.com"
+        );
+    }
+
+    #[test]
+    fn test_reject_disposable() {
+        let policy = EmailPolicy::new().reject_disposable();
+        assert_eq!(
+            Email::parse_with_policy(&policy, "jane@Inbox.Mailinator.com"),
+            Err(TypeError::Email(EmailError::DisposableDomain {
+                domain: "Inbox.Mailinator.com".to_string()
+            }))
+        );
+        // Grammar errors take precedence.
+        assert_eq!(
+            Email::parse_with_policy(&policy, "jane@mailinator..com"),
+            Err(TypeError::Email(EmailError::InvalidDomain { position: 16 }))
         );
+        assert!(Email::parse_with_policy(&policy, "jane@[192.0.2.1]").is_ok());
+    }
+
+    #[test]
+    fn test_canonical_decodes_punycode() {
+        let unicode = Email::parse("user@Exämple.de").unwrap();
+        let ascii = Email::parse("user@XN--EXMPLE-CUA.de").unwrap();
+        assert_eq!(unicode.canonical(), "user@exämple.de");
+        assert_eq!(unicode.canonical(), ascii.canonical());
     }
 }
@@ -0,0 +1,171 @@
+use serde::{Deserialize, Serialize};
+use unicode_normalization::UnicodeNormalization;
+
+/// Turns the local part and domain of an address into the form used to
+/// decide whether two addresses reach the same mailbox. Implement it to plug
+/// in rules of your own; [`Canonicalization`] provides the built-in ones.
+///
+/// Implementations receive the local part as typed and the domain already
+/// lowercased in its Unicode form, and return the canonical address.
+///
+/// # Example
+///
+/// ```
+/// use custom_type::{Canonicalize, Email};
+///
+/// /// Treats `-` as the tag separator, as Yahoo does.
+/// struct DashTags;
+///
+/// impl Canonicalize for DashTags {
+///     fn canonicalize(&self, local_part: &str, domain: &str) -> String {
+///         let local_part = local_part.split('-').next().unwrap_or(local_part);
+///         format!("{}@{}", local_part.to_lowercase(), domain)
+///     }
+/// }
+///
+/// let email = Email::parse("Jane-news@Yahoo.com").unwrap();
+/// assert_eq!(email.canonical_with(&DashTags), "jane@yahoo.com");
+/// ```
+pub trait Canonicalize {
+    /// Returns the canonical address for `local_part` at `domain`.
+    fn canonicalize(&self, local_part: &str, domain: &str) -> String;
+}
+
+/// The built-in canonicalization strategies for [`Email::canonical_with`](crate::Email::canonical_with).
+///
+/// Every strategy lowercases the domain, decodes punycode labels, and
+/// normalizes the local part to Unicode NFC.
+///
+/// # Example
+///
+/// ```
+/// use custom_type::{Canonicalization, Email};
+///
+/// let email = Email::parse("Jane.Doe+news@GoogleMail.com").unwrap();
+/// assert_eq!(email.canonical(), "Jane.Doe+news@googlemail.com");
+/// assert_eq!(
+///     email.canonical_with(&Canonicalization::Lowercase),
+///     "jane.doe+news@googlemail.com"
+/// );
+/// assert_eq!(
+///     email.canonical_with(&Canonicalization::ProviderAware),
+///     "janedoe@gmail.com"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Canonicalization {
+    /// Lowercases only the domain; local parts are case-sensitive per RFC 5321.
+    #[default]
+    DomainOnly,
+    /// Lowercases the whole address, as almost every mail provider ignores
+    /// the case of the local part.
+    Lowercase,
+    /// Applies the rules of well-known providers: Gmail ignores dots and
+    /// `+tags` and treats `googlemail.com` as `gmail.com`; Outlook and
+    /// Fastmail ignore `+tags`. Every provider listed is case-insensitive.
+    /// Other domains are only lowercased.
+    ProviderAware,
+}
+
+/// Providers whose rules [`Canonicalization::ProviderAware`] knows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Provider {
+    Gmail,
+    Outlook,
+    Fastmail,
+}
+
+/// Domains of each known provider, with the domain they canonicalize to.
+const PROVIDERS: [(&str, Provider, &str); 9] = [
+    ("gmail.com", Provider::Gmail, "gmail.com"),
+    ("googlemail.com", Provider::Gmail, "gmail.com"),
+    ("outlook.com", Provider::Outlook, "outlook.com"),
+    ("hotmail.com", Provider::Outlook, "hotmail.com"),
+    ("live.com", Provider::Outlook, "live.com"),
+    ("msn.com", Provider::Outlook, "msn.com"),
+    ("fastmail.com", Provider::Fastmail, "fastmail.com"),
+    ("fastmail.fm", Provider::Fastmail, "fastmail.fm"),
+    (
+        "messagingengine.com",
+        Provider::Fastmail,
+        "messagingengine.com",
+    ),
+];
+
+impl Canonicalize for Canonicalization {
+    fn canonicalize(&self, local_part: &str, domain: &str) -> String {
+        let local_part: String = local_part.nfc().collect();
+        match self {
+            Canonicalization::DomainOnly => format!("{}@{}", local_part, domain),
+            Canonicalization::Lowercase => {
+                format!("{}@{}", local_part.to_lowercase(), domain)
+            }
+            Canonicalization::ProviderAware => {
+                let local_part = local_part.to_lowercase();
+                let Some((_, provider, domain)) =
+                    PROVIDERS.iter().find(|(known, _, _)| *known == domain)
+                else {
+                    return format!("{}@{}", local_part, domain);
+                };
+                let local_part = strip_tag(&local_part);
+                match provider {
+                    Provider::Gmail => format!("{}@{}", local_part.replace('.', ""), domain),
+                    Provider::Outlook | Provider::Fastmail => {
+                        format!("{}@{}", local_part, domain)
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Removes a `+tag` suffix from an unquoted local part.
+fn strip_tag(local_part: &str) -> &str {
+    if local_part.starts_with('"') {
+        return local_part;
+    }
+    match local_part.split_once('+') {
+        Some((base, _)) if !base.is_empty() => base,
+        _ => local_part,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_domain_only() {
+        assert_eq!(
+            Canonicalization::DomainOnly.canonicalize("Jane.Doe", "example.com"),
+            "Jane.Doe@example.com"
+        );
+        // Composed and decomposed spellings canonicalize alike.
+        assert_eq!(
+            Canonicalization::DomainOnly.canonicalize("jose\u{301}", "example.com"),
+            "jos\u{e9}@example.com"
+        );
+    }
+
+    #[test]
+    fn test_provider_aware() {
+        let strategy = Canonicalization::ProviderAware;
+        assert_eq!(
+            strategy.canonicalize("J.Doe+spam", "googlemail.com"),
+            "jdoe@gmail.com"
+        );
+        assert_eq!(
+            strategy.canonicalize("jane+news", "outlook.com"),
+            "jane@outlook.com"
+        );
+        assert_eq!(
+            strategy.canonicalize("Jane.Doe+work", "fastmail.com"),
+            "jane.doe@fastmail.com"
+        );
+        assert_eq!(
+            strategy.canonicalize("jane.doe+work", "example.com"),
+            "jane.doe+work@example.com"
+        );
+        assert_eq!(strategy.canonicalize("+tag", "gmail.com"), "+tag@gmail.com");
+    }
+}
@@ -0,0 +1,187 @@
+use crate::email::disposable::{normalize, read_list};
+use std::collections::HashSet;
+use std::io;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Local parts that name a function rather than a person.
+const ROLE_ACCOUNTS: &str = include_str!("data/role_accounts.txt");
+
+/// Domains of free webmail providers anyone can sign up with.
+const FREE_PROVIDERS: &str = include_str!("data/free_providers.txt");
+
+/// ### RoleAccounts : Shared Mailbox Names
+/// Local parts such as `admin`, `noreply` and `support` that reach a role or
+/// a team rather than a person. Names are compared in lowercase, after
+/// dropping a `+tag`, so `Support+billing@example.com` is a role account.
+///
+/// # Example
+///
+/// ```
+/// use custom_type::{Email, RoleAccounts};
+///
+/// let mut roles = RoleAccounts::embedded();
+/// roles.insert("recruiting");
+///
+/// let email = Email::parse("Recruiting@example.com").unwrap();
+/// assert!(!email.is_role_account());
+/// assert!(email.is_role_account_in(&roles));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RoleAccounts(HashSet<String>);
+
+/// ### FreeProviders : Free Webmail Domains
+/// Domains such as `gmail.com`, `yahoo.com` and `outlook.com` where anyone
+/// can create an address, as opposed to corporate domains. Domains are
+/// compared in lowercase ASCII form.
+///
+/// # Example
+///
+/// ```
+/// use custom_type::{Email, FreeProviders};
+///
+/// let mut providers = FreeProviders::embedded();
+/// providers.insert("mail.example");
+///
+/// let email = Email::parse("jane@Mail.Example").unwrap();
+/// assert!(!email.is_free_provider());
+/// assert!(email.is_free_provider_in(&providers));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FreeProviders(HashSet<String>);
+
+impl RoleAccounts {
+    /// Creates an empty list that matches no local part.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a list holding the names built into the crate.
+    pub fn embedded() -> Self {
+        builtin_roles().clone()
+    }
+
+    /// Adds a role name.
+    pub fn insert(&mut self, name: &str) {
+        self.0.insert(name.trim().to_lowercase());
+    }
+
+    /// Adds every name listed in the file at `path`, one per line; blank
+    /// lines and lines starting with `#` are skipped.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` with the number of names read.
+    /// * `Err(io::Error)` if the file cannot be read.
+    pub fn load(&mut self, path: impl AsRef<Path>) -> io::Result<usize> {
+        let names = read_list(path.as_ref())?;
+        for name in &names {
+            self.insert(name);
+        }
+        Ok(names.len())
+    }
+
+    /// Returns `true` if `local_part`, without its `+tag`, is a role name.
+    pub fn contains(&self, local_part: &str) -> bool {
+        let local_part = local_part.to_lowercase();
+        let name = match local_part.split_once('+') {
+            Some((name, _)) if !name.is_empty() => name,
+            _ => &local_part,
+        };
+        self.0.contains(name)
+    }
+}
+
+impl FreeProviders {
+    /// Creates an empty list that matches no domain.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a list holding the domains built into the crate.
+    pub fn embedded() -> Self {
+        builtin_providers().clone()
+    }
+
+    /// Adds a provider domain.
+    pub fn insert(&mut self, domain: &str) {
+        self.0.insert(normalize(domain));
+    }
+
+    /// Adds every domain listed in the file at `path`, in the format of
+    /// [`RoleAccounts::load`].
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` with the number of domains read.
+    /// * `Err(io::Error)` if the file cannot be read.
+    pub fn load(&mut self, path: impl AsRef<Path>) -> io::Result<usize> {
+        let domains = read_list(path.as_ref())?;
+        for domain in &domains {
+            self.insert(domain);
+        }
+        Ok(domains.len())
+    }
+
+    /// Returns `true` if `domain` is a provider domain.
+    pub fn contains(&self, domain: &str) -> bool {
+        self.0.contains(&normalize(domain))
+    }
+}
+
+/// Returns the role names built into the crate.
+pub(crate) fn builtin_roles() -> &'static RoleAccounts {
+    static BUILTIN: OnceLock<RoleAccounts> = OnceLock::new();
+    BUILTIN.get_or_init(|| RoleAccounts(ROLE_ACCOUNTS.lines().map(str::to_string).collect()))
+}
+
+/// Returns the provider domains built into the crate.
+pub(crate) fn builtin_providers() -> &'static FreeProviders {
+    static BUILTIN: OnceLock<FreeProviders> = OnceLock::new();
+    BUILTIN.get_or_init(|| FreeProviders(FREE_PROVIDERS.lines().map(normalize).collect()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_role_accounts() {
+        let roles = builtin_roles();
+        assert!(roles.contains("admin"));
+        assert!(roles.contains("NoReply"));
+        assert!(roles.contains("no-reply"));
+        assert!(roles.contains("support+billing"));
+        assert!(!roles.contains("jane.doe"));
+        assert!(!roles.contains("administrators"));
+        assert!(!roles.contains("+admin"));
+    }
+
+    #[test]
+    fn test_free_providers() {
+        let providers = builtin_providers();
+        assert!(providers.contains("gmail.com"));
+        assert!(providers.contains("Yahoo.COM."));
+        assert!(providers.contains("outlook.com"));
+        assert!(!providers.contains("example.com"));
+        assert!(!providers.contains("mail.gmail.com"));
+    }
+
+    #[test]
+    fn test_load() {
+        let path = std::env::temp_dir().join(format!("roles-{}.txt", std::process::id()));
+        std::fs::write(&path, "# team inboxes\nRecruiting\n\nlegal\n").unwrap();
+
+        let mut roles = RoleAccounts::new();
+        assert_eq!(roles.load(&path).unwrap(), 2);
+        assert!(roles.contains("recruiting"));
+        assert!(roles.contains("Legal"));
+        assert!(!roles.contains("admin"));
+
+        let mut providers = FreeProviders::new();
+        assert_eq!(providers.load(&path).unwrap(), 2);
+        assert!(providers.contains("legal"));
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,201 @@
+use crate::email::parser;
+use std::collections::HashSet;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Domains of well-known throwaway mail services, one per line, in the format
+/// of the community `disposable-email-domains` blocklist so it can be refreshed
+/// from there.
+const DISPOSABLE_DOMAINS: &str = include_str!("data/disposable_domains.txt");
+
+/// ### DisposableDomains : Throwaway Email Domains
+/// A blocklist of disposable email domains and an allowlist of exceptions.
+/// A domain matches if it or any parent domain is blocked, so
+/// `inbox.mailinator.com` matches `mailinator.com`, unless it or a parent
+/// domain is allowed. Domains are compared in lowercase ASCII form.
+///
+/// [`DisposableDomains::embedded`] starts from the list built into the crate;
+/// more entries can be added in code or loaded from files at runtime.
+///
+/// # Example
+///
+/// ```
+/// use custom_type::DisposableDomains;
+///
+/// let mut domains = DisposableDomains::embedded();
+/// domains.block("throwaway.example");
+/// domains.allow("safe.mailinator.com");
+///
+/// assert!(domains.contains("inbox.mailinator.com"));
+/// assert!(domains.contains("Throwaway.Example"));
+/// assert!(!domains.contains("safe.mailinator.com"));
+/// assert!(!domains.contains("example.com"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DisposableDomains {
+    blocked: HashSet<String>,
+    allowed: HashSet<String>,
+}
+
+impl DisposableDomains {
+    /// Creates an empty list that matches no domain.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a list holding the domains built into the crate.
+    pub fn embedded() -> Self {
+        builtin().clone()
+    }
+
+    /// Blocks `domain` and its subdomains.
+    pub fn block(&mut self, domain: &str) {
+        self.blocked.insert(normalize(domain));
+    }
+
+    /// Allows `domain` and its subdomains, even if a parent domain is blocked.
+    pub fn allow(&mut self, domain: &str) {
+        self.allowed.insert(normalize(domain));
+    }
+
+    /// Blocks every domain listed in the file at `path`.
+    ///
+    /// The file holds one domain per line; blank lines and lines starting
+    /// with `#` are skipped.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` with the number of domains read.
+    /// * `Err(io::Error)` if the file cannot be read.
+    pub fn load_blocklist(&mut self, path: impl AsRef<Path>) -> io::Result<usize> {
+        let domains = read_domains(path.as_ref())?;
+        let count = domains.len();
+        self.blocked.extend(domains);
+        Ok(count)
+    }
+
+    /// Allows every domain listed in the file at `path`, in the format of
+    /// [`DisposableDomains::load_blocklist`].
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` with the number of domains read.
+    /// * `Err(io::Error)` if the file cannot be read.
+    pub fn load_allowlist(&mut self, path: impl AsRef<Path>) -> io::Result<usize> {
+        let domains = read_domains(path.as_ref())?;
+        let count = domains.len();
+        self.allowed.extend(domains);
+        Ok(count)
+    }
+
+    /// Returns `true` if `domain` or a parent domain is blocked and neither
+    /// is allowed.
+    pub fn contains(&self, domain: &str) -> bool {
+        if domain.starts_with('[') {
+            return false;
+        }
+        let domain = normalize(domain);
+        let mut blocked = false;
+        for suffix in suffixes(&domain) {
+            if self.allowed.contains(suffix) {
+                return false;
+            }
+            blocked |= self.blocked.contains(suffix);
+        }
+        blocked
+    }
+}
+
+/// Returns the list built into the crate.
+pub(crate) fn builtin() -> &'static DisposableDomains {
+    static BUILTIN: OnceLock<DisposableDomains> = OnceLock::new();
+    BUILTIN.get_or_init(|| DisposableDomains {
+        blocked: DISPOSABLE_DOMAINS.lines().map(normalize).collect(),
+        allowed: HashSet::new(),
+    })
+}
+
+/// Returns `domain` and each of its parent domains, longest first.
+fn suffixes(domain: &str) -> impl Iterator<Item = &str> {
+    std::iter::successors(Some(domain), |domain| {
+        domain.split_once('.').map(|(_, parent)| parent)
+    })
+}
+
+/// Returns `domain` in lowercase ASCII form without a trailing dot.
+pub(crate) fn normalize(domain: &str) -> String {
+    parser::to_ascii(domain.trim().trim_end_matches('.'))
+}
+
+fn read_domains(path: &Path) -> io::Result<Vec<String>> {
+    Ok(read_list(path)?
+        .iter()
+        .map(|domain| normalize(domain))
+        .collect())
+}
+
+/// Reads a list file: one entry per line, skipping blank lines and lines
+/// starting with `#`.
+pub(crate) fn read_list(path: &Path) -> io::Result<Vec<String>> {
+    let mut entries = Vec::new();
+    for line in BufReader::new(std::fs::File::open(path)?).lines() {
+        let line = line?;
+        let line = line.trim();
+        if !line.is_empty() && !line.starts_with('#') {
+            entries.push(line.to_string());
+        }
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embedded_list() {
+        let domains = builtin();
+        assert!(domains.contains("mailinator.com"));
+        assert!(domains.contains("MAILINATOR.COM."));
+        assert!(domains.contains("a.b.yopmail.com"));
+        assert!(!domains.contains("notmailinator.com"));
+        assert!(!domains.contains("com"));
+        assert!(!domains.contains("[192.0.2.1]"));
+    }
+
+    #[test]
+    fn test_allowlist_overrides_blocklist() {
+        let mut domains = DisposableDomains::new();
+        domains.block("example.com");
+        domains.allow("corp.example.com");
+        assert!(domains.contains("example.com"));
+        assert!(domains.contains("mail.example.com"));
+        assert!(!domains.contains("corp.example.com"));
+        assert!(!domains.contains("eu.corp.example.com"));
+    }
+
+    #[test]
+    fn test_load_files() {
+        let dir = std::env::temp_dir().join(format!("disposable-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let blocklist = dir.join("block.txt");
+        let allowlist = dir.join("allow.txt");
+        std::fs::write(
+            &blocklist,
+            "# extra domains\nspam.example\n\nBÜCHER.example\n",
+        )
+        .unwrap();
+        std::fs::write(&allowlist, "mailinator.com\n").unwrap();
+
+        let mut domains = DisposableDomains::embedded();
+        assert_eq!(domains.load_blocklist(&blocklist).unwrap(), 2);
+        assert_eq!(domains.load_allowlist(&allowlist).unwrap(), 1);
+        assert!(domains.contains("spam.example"));
+        assert!(domains.contains("xn--bcher-kva.example"));
+        assert!(!domains.contains("mailinator.com"));
+        assert!(domains.load_blocklist(dir.join("missing.txt")).is_err());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
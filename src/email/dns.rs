@@ -0,0 +1,356 @@
+use crate::Email;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::io;
+use std::net::IpAddr;
+use std::pin::Pin;
+
+/// The future returned by [`DnsResolver`] lookups.
+pub type DnsFuture<'a, T> = Pin<Box<dyn Future<Output = io::Result<T>> + Send + 'a>>;
+
+/// ### DnsResolver : Pluggable DNS Lookups
+/// Looks up the DNS records [`Email::verify_mx`] needs. Implement it over
+/// the resolver your application already uses, or over an in-memory zone in
+/// tests. With the `hickory` feature, [`HickoryResolver`] queries the
+/// system's name servers.
+///
+/// Domains are passed in lowercase ASCII form without a trailing dot.
+/// Lookups return an empty list when the domain exists but has no records of
+/// the type, and an error of kind [`io::ErrorKind::NotFound`] when the domain
+/// does not exist (NXDOMAIN).
+///
+/// # Example
+///
+/// ```
+/// use custom_type::{DnsFuture, DnsResolver, MxRecord};
+/// use std::io;
+/// use std::net::IpAddr;
+///
+/// /// Every domain accepts mail at `mx.example.net`.
+/// struct CatchAll;
+///
+/// impl DnsResolver for CatchAll {
+///     fn mx<'a>(&'a self, _domain: &'a str) -> DnsFuture<'a, Vec<MxRecord>> {
+///         Box::pin(async { Ok(vec![MxRecord::new(10, "mx.example.net")]) })
+///     }
+///
+///     fn ip<'a>(&'a self, _domain: &'a str) -> DnsFuture<'a, Vec<IpAddr>> {
+///         Box::pin(async { Ok(Vec::new()) })
+///     }
+/// }
+/// ```
+pub trait DnsResolver: Send + Sync {
+    /// Returns the MX records of `domain`.
+    fn mx<'a>(&'a self, domain: &'a str) -> DnsFuture<'a, Vec<MxRecord>>;
+
+    /// Returns the A and AAAA addresses of `domain`.
+    fn ip<'a>(&'a self, domain: &'a str) -> DnsFuture<'a, Vec<IpAddr>>;
+}
+
+/// A mail exchanger published in an MX record.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MxRecord {
+    /// Lower values are tried first.
+    pub preference: u16,
+    /// The host name of the mail server, without a trailing dot; empty for
+    /// the root domain `.` used by a null MX.
+    pub exchange: String,
+}
+
+impl MxRecord {
+    /// Creates a record, lowercasing `exchange` and removing its trailing dot.
+    pub fn new(preference: u16, exchange: impl ToString) -> Self {
+        Self {
+            preference,
+            exchange: exchange
+                .to_string()
+                .trim_end_matches('.')
+                .to_ascii_lowercase(),
+        }
+    }
+
+    /// Returns `true` for a null MX (RFC 7505), whose exchange is the root
+    /// domain.
+    pub fn is_null(&self) -> bool {
+        self.exchange.is_empty()
+    }
+}
+
+/// ### Deliverability : Result of an MX Lookup
+/// Where mail for a domain would be delivered, as found by
+/// [`Email::verify_mx`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Deliverability {
+    /// The domain publishes MX records, sorted by preference.
+    Mx(Vec<MxRecord>),
+    /// The domain publishes no MX records, so mail goes to its own A and
+    /// AAAA addresses (RFC 5321 section 5.1), or to its address literal.
+    Addresses(Vec<IpAddr>),
+    /// The domain publishes a null MX (RFC 7505) and accepts no mail.
+    NullMx,
+    /// The domain exists but has neither MX nor address records.
+    NoMailServer,
+    /// The domain does not exist.
+    DomainNotFound,
+}
+
+impl Deliverability {
+    /// Returns `true` if the domain has somewhere to deliver mail.
+    pub fn is_deliverable(&self) -> bool {
+        matches!(self, Deliverability::Mx(_) | Deliverability::Addresses(_))
+    }
+}
+
+impl Email {
+    /// Looks up where mail for the address's domain would be delivered: its
+    /// MX records, falling back to its A and AAAA addresses when it has none.
+    /// Address literals are not looked up.
+    ///
+    /// # Arguments
+    ///
+    /// * `resolver` - The DNS resolver to query.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Deliverability)` describing the domain's mail setup.
+    /// * `Err(io::Error)` if a lookup fails for a reason other than the
+    ///   domain or its records not existing.
+    pub async fn verify_mx(&self, resolver: &dyn DnsResolver) -> io::Result<Deliverability> {
+        if let Some(ip) = address_literal(self.domain()) {
+            return Ok(Deliverability::Addresses(vec![ip]));
+        }
+        let domain = self.domain_ascii();
+        let domain = domain.trim_end_matches('.');
+
+        let mut records = match resolver.mx(domain).await {
+            Ok(records) => records,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                return Ok(Deliverability::DomainNotFound)
+            }
+            Err(err) => return Err(err),
+        };
+        if records.iter().any(MxRecord::is_null) {
+            return Ok(Deliverability::NullMx);
+        }
+        if !records.is_empty() {
+            records.sort_by_key(|record| record.preference);
+            return Ok(Deliverability::Mx(records));
+        }
+
+        match resolver.ip(domain).await {
+            Ok(addresses) if addresses.is_empty() => Ok(Deliverability::NoMailServer),
+            Ok(addresses) => Ok(Deliverability::Addresses(addresses)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Deliverability::DomainNotFound),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+/// Returns the address of a `[192.0.2.1]` or `[IPv6:2001:db8::1]` literal.
+fn address_literal(domain: &str) -> Option<IpAddr> {
+    let literal = domain.strip_prefix('[')?.strip_suffix(']')?;
+    match literal.get(..5) {
+        Some(tag) if tag.eq_ignore_ascii_case("IPv6:") => literal[5..].parse().ok(),
+        _ => literal.parse().ok(),
+    }
+}
+
+/// ### HickoryResolver : System DNS Resolver
+/// A [`DnsResolver`] backed by the `hickory-resolver` crate on the Tokio
+/// runtime, using the system's resolver configuration.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn run() -> std::io::Result<()> {
+/// use custom_type::{Email, HickoryResolver};
+///
+/// let resolver = HickoryResolver::from_system_conf()?;
+/// let email = Email::parse("jane@example.com").unwrap();
+/// if !email.verify_mx(&resolver).await?.is_deliverable() {
+///     println!("{} cannot receive mail", email.domain());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "hickory")]
+#[derive(Clone)]
+pub struct HickoryResolver(hickory_resolver::TokioAsyncResolver);
+
+#[cfg(feature = "hickory")]
+impl HickoryResolver {
+    /// Creates a resolver from `/etc/resolv.conf` or the Windows registry.
+    pub fn from_system_conf() -> io::Result<Self> {
+        hickory_resolver::TokioAsyncResolver::tokio_from_system_conf()
+            .map(Self)
+            .map_err(io::Error::other)
+    }
+
+    /// Wraps an existing hickory resolver.
+    pub fn new(resolver: hickory_resolver::TokioAsyncResolver) -> Self {
+        Self(resolver)
+    }
+}
+
+#[cfg(feature = "hickory")]
+impl std::fmt::Debug for HickoryResolver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HickoryResolver").finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "hickory")]
+impl DnsResolver for HickoryResolver {
+    fn mx<'a>(&'a self, domain: &'a str) -> DnsFuture<'a, Vec<MxRecord>> {
+        Box::pin(async move {
+            match self.0.mx_lookup(format!("{}.", domain)).await {
+                Ok(lookup) => Ok(lookup
+                    .iter()
+                    .map(|mx| MxRecord::new(mx.preference(), mx.exchange()))
+                    .collect()),
+                Err(err) => empty_or_error(err),
+            }
+        })
+    }
+
+    fn ip<'a>(&'a self, domain: &'a str) -> DnsFuture<'a, Vec<IpAddr>> {
+        Box::pin(async move {
+            match self.0.lookup_ip(format!("{}.", domain)).await {
+                Ok(lookup) => Ok(lookup.iter().collect()),
+                Err(err) => empty_or_error(err),
+            }
+        })
+    }
+}
+
+/// Maps "no records" to an empty list and NXDOMAIN to [`io::ErrorKind::NotFound`].
+#[cfg(feature = "hickory")]
+fn empty_or_error<T>(err: hickory_resolver::error::ResolveError) -> io::Result<Vec<T>> {
+    use hickory_resolver::error::ResolveErrorKind;
+    use hickory_resolver::proto::op::ResponseCode;
+
+    match err.kind() {
+        ResolveErrorKind::NoRecordsFound {
+            response_code: ResponseCode::NXDomain,
+            ..
+        } => Err(io::Error::new(io::ErrorKind::NotFound, err)),
+        ResolveErrorKind::NoRecordsFound { .. } => Ok(Vec::new()),
+        _ => Err(io::Error::other(err)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::block_on;
+    use std::collections::HashMap;
+
+    /// An in-memory zone; domains missing from both maps do not exist.
+    #[derive(Default)]
+    struct Zone {
+        mx: HashMap<&'static str, Vec<MxRecord>>,
+        ip: HashMap<&'static str, Vec<IpAddr>>,
+        failing: bool,
+    }
+
+    impl Zone {
+        fn lookup<T: Clone>(
+            &self,
+            records: &HashMap<&str, Vec<T>>,
+            domain: &str,
+        ) -> io::Result<Vec<T>> {
+            if self.failing {
+                return Err(io::Error::new(io::ErrorKind::TimedOut, "timed out"));
+            }
+            if !self.mx.contains_key(domain) && !self.ip.contains_key(domain) {
+                return Err(io::Error::new(io::ErrorKind::NotFound, "NXDOMAIN"));
+            }
+            Ok(records.get(domain).cloned().unwrap_or_default())
+        }
+    }
+
+    impl DnsResolver for Zone {
+        fn mx<'a>(&'a self, domain: &'a str) -> DnsFuture<'a, Vec<MxRecord>> {
+            Box::pin(async move { self.lookup(&self.mx, domain) })
+        }
+
+        fn ip<'a>(&'a self, domain: &'a str) -> DnsFuture<'a, Vec<IpAddr>> {
+            Box::pin(async move { self.lookup(&self.ip, domain) })
+        }
+    }
+
+    fn zone() -> Zone {
+        let mut zone = Zone::default();
+        zone.mx.insert(
+            "example.com",
+            vec![
+                MxRecord::new(20, "backup.example.com."),
+                MxRecord::new(10, "MX.example.com."),
+            ],
+        );
+        zone.mx
+            .insert("nomail.example", vec![MxRecord::new(0, ".")]);
+        zone.ip
+            .insert("direct.example", vec!["192.0.2.1".parse().unwrap()]);
+        zone.ip.insert("empty.example", Vec::new());
+        zone.mx.insert(
+            "xn--exmple-cua.de",
+            vec![MxRecord::new(10, "mx.xn--exmple-cua.de")],
+        );
+        zone
+    }
+
+    fn verify(zone: &Zone, email: &str) -> io::Result<Deliverability> {
+        block_on(Email::parse(email).unwrap().verify_mx(zone))
+    }
+
+    #[test]
+    fn test_mx_records_sorted_by_preference() {
+        assert_eq!(
+            verify(&zone(), "jane@Example.COM").unwrap(),
+            Deliverability::Mx(vec![
+                MxRecord::new(10, "mx.example.com"),
+                MxRecord::new(20, "backup.example.com"),
+            ])
+        );
+        assert!(verify(&zone(), "jane@exämple.de").unwrap().is_deliverable());
+    }
+
+    #[test]
+    fn test_fallbacks() {
+        let zone = zone();
+        assert_eq!(
+            verify(&zone, "jane@direct.example").unwrap(),
+            Deliverability::Addresses(vec!["192.0.2.1".parse().unwrap()])
+        );
+        assert_eq!(
+            verify(&zone, "jane@nomail.example").unwrap(),
+            Deliverability::NullMx
+        );
+        assert_eq!(
+            verify(&zone, "jane@empty.example").unwrap(),
+            Deliverability::NoMailServer
+        );
+        assert_eq!(
+            verify(&zone, "jane@missing.example").unwrap(),
+            Deliverability::DomainNotFound
+        );
+        assert!(!Deliverability::NullMx.is_deliverable());
+    }
+
+    #[test]
+    fn test_address_literals_are_not_looked_up() {
+        let zone = Zone {
+            failing: true,
+            ..Zone::default()
+        };
+        assert_eq!(
+            verify(&zone, "jane@[IPv6:2001:db8::1]").unwrap(),
+            Deliverability::Addresses(vec!["2001:db8::1".parse().unwrap()])
+        );
+        assert_eq!(
+            verify(&zone, "jane@example.com").unwrap_err().kind(),
+            io::ErrorKind::TimedOut
+        );
+    }
+}
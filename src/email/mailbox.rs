@@ -0,0 +1,714 @@
+use crate::email::parser::{self, is_atext};
+use crate::email::{Email, EmailMode};
+use crate::error::{EmailError, TypeError};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// ### Mailbox : Address With Display Name
+/// An [`Email`] with an optional display name, as in the `From:`, `To:` and
+/// `Cc:` headers of RFC 5322: `"Doe, John" <john@example.com>`.
+///
+/// `Display` quotes and escapes the name only when it needs it, so the output
+/// is always a valid header value that parses back to the same mailbox.
+///
+/// # Example
+///
+/// ```
+/// use custom_type::Mailbox;
+///
+/// let mailbox = Mailbox::parse("\"Doe, John\" <john@example.com>").unwrap();
+/// assert_eq!(mailbox.name(), Some("Doe, John"));
+/// assert_eq!(mailbox.email().as_str(), "john@example.com");
+/// assert_eq!(mailbox.to_string(), "\"Doe, John\" <john@example.com>");
+///
+/// let mailbox = Mailbox::parse("Jane Doe <jane@example.com>").unwrap();
+/// assert_eq!(mailbox.to_string(), "Jane Doe <jane@example.com>");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Mailbox {
+    name: Option<String>,
+    email: Email,
+}
+
+/// A named group of mailboxes, as in `Team: jane@example.com, john@example.com;`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Group {
+    name: String,
+    members: Vec<Mailbox>,
+}
+
+/// One entry of an [`AddressList`]: a mailbox or a group of mailboxes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Address {
+    Mailbox(Mailbox),
+    Group(Group),
+}
+
+/// ### AddressList : Comma-Separated Addresses
+/// The RFC 5322 `address-list` of `To:` and `Cc:` headers: mailboxes and
+/// groups separated by commas. Comments are skipped, and empty entries such
+/// as a trailing comma in a CSV import are ignored.
+///
+/// # Example
+///
+/// ```
+/// use custom_type::AddressList;
+///
+/// let list = AddressList::parse(
+///     "\"Doe, John\" <john@example.com>, jane@example.com, Team: a@example.com;",
+/// )
+/// .unwrap();
+///
+/// let emails: Vec<&str> = list.mailboxes().map(|m| m.email().as_str()).collect();
+/// assert_eq!(emails, ["john@example.com", "jane@example.com", "a@example.com"]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct AddressList(Vec<Address>);
+
+impl Mailbox {
+    /// Creates a mailbox from a display name and an address.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` if the name contains no control characters.
+    /// * `Err(TypeError::Email)` with [`EmailError::InvalidDisplayName`] otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use custom_type::{Email, Mailbox};
+    ///
+    /// let email = Email::parse("jane@example.com").unwrap();
+    /// let mailbox = Mailbox::new(Some("Jane \"JD\" Doe"), email.clone()).unwrap();
+    /// assert_eq!(mailbox.to_string(), "\"Jane \\\"JD\\\" Doe\" <jane@example.com>");
+    ///
+    /// assert!(Mailbox::new(Some("Jane\r\nBcc: x@example.com"), email).is_err());
+    /// ```
+    pub fn new(name: Option<impl ToString>, email: Email) -> Result<Self, TypeError> {
+        let name = name.map(|name| name.to_string());
+        if let Some(position) = name
+            .as_deref()
+            .and_then(|name| name.chars().position(char::is_control))
+        {
+            return Err(EmailError::InvalidDisplayName { position }.into());
+        }
+        Ok(Self { name, email })
+    }
+
+    /// Parses a single mailbox: a bare address, `<address>`, or
+    /// `Display Name <address>`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` if the input is exactly one valid mailbox.
+    /// * `Err(TypeError::Email)` otherwise.
+    pub fn parse(mailbox: impl ToString) -> Result<Self, TypeError> {
+        let chars: Vec<char> = mailbox.to_string().chars().collect();
+        let mut cursor = Cursor::new(&chars);
+        let mailbox = cursor.mailbox()?;
+        cursor.end()?;
+        Ok(mailbox)
+    }
+
+    /// Returns the display name, if any.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the address.
+    pub fn email(&self) -> &Email {
+        &self.email
+    }
+}
+
+impl From<Email> for Mailbox {
+    fn from(email: Email) -> Self {
+        Self { name: None, email }
+    }
+}
+
+impl Group {
+    /// Creates a group from a name and its members.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` if the name contains no control characters.
+    /// * `Err(TypeError::Email)` with [`EmailError::InvalidDisplayName`] otherwise.
+    pub fn new(name: impl ToString, members: Vec<Mailbox>) -> Result<Self, TypeError> {
+        let name = name.to_string();
+        if let Some(position) = name.chars().position(char::is_control) {
+            return Err(EmailError::InvalidDisplayName { position }.into());
+        }
+        Ok(Self { name, members })
+    }
+
+    /// Returns the group name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the members of the group, which may be empty.
+    pub fn members(&self) -> &[Mailbox] {
+        &self.members
+    }
+}
+
+impl AddressList {
+    /// Parses a comma-separated list of mailboxes and groups.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` if every entry is valid.
+    /// * `Err(TypeError::Email)` with [`EmailError::InvalidAddressList`] for
+    ///   malformed syntax, or [`EmailError::InvalidMailbox`] for an invalid
+    ///   address, both giving the position in the input.
+    pub fn parse(list: impl ToString) -> Result<Self, TypeError> {
+        let chars: Vec<char> = list.to_string().chars().collect();
+        Ok(Self(Cursor::new(&chars).address_list()?))
+    }
+
+    /// Returns the entries of the list.
+    pub fn addresses(&self) -> &[Address] {
+        &self.0
+    }
+
+    /// Returns every mailbox in the list, including the members of groups.
+    pub fn mailboxes(&self) -> impl Iterator<Item = &Mailbox> {
+        self.0.iter().flat_map(|address| match address {
+            Address::Mailbox(mailbox) => std::slice::from_ref(mailbox),
+            Address::Group(group) => group.members(),
+        })
+    }
+
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the list has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<Vec<Address>> for AddressList {
+    fn from(addresses: Vec<Address>) -> Self {
+        Self(addresses)
+    }
+}
+
+impl IntoIterator for AddressList {
+    type Item = Address;
+    type IntoIter = std::vec::IntoIter<Address>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl fmt::Display for Mailbox {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.name {
+            Some(name) => {
+                write_phrase(f, name)?;
+                write!(f, " <{}>", self.email)
+            }
+            None => write!(f, "{}", self.email),
+        }
+    }
+}
+
+impl fmt::Display for Group {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_phrase(f, &self.name)?;
+        write!(f, ":")?;
+        for (i, member) in self.members.iter().enumerate() {
+            let separator = if i == 0 { " " } else { ", " };
+            write!(f, "{}{}", separator, member)?;
+        }
+        write!(f, ";")
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Address::Mailbox(mailbox) => mailbox.fmt(f),
+            Address::Group(group) => group.fmt(f),
+        }
+    }
+}
+
+impl fmt::Display for AddressList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, address) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            address.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl Serialize for Mailbox {
+    /// Serializes the mailbox in its header form.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Mailbox {
+    /// Deserializes a string and validates it with [`Mailbox::parse`].
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mailbox = String::deserialize(deserializer)?;
+        Self::parse(mailbox).map_err(serde::de::Error::custom)
+    }
+}
+
+impl Serialize for AddressList {
+    /// Serializes the list in its header form.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for AddressList {
+    /// Deserializes a string and validates it with [`AddressList::parse`].
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let list = String::deserialize(deserializer)?;
+        Self::parse(list).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Writes a display name as atoms when it is a run of single-space separated
+/// atoms, and as a quoted string otherwise.
+fn write_phrase(f: &mut fmt::Formatter<'_>, phrase: &str) -> fmt::Result {
+    let is_atoms = !phrase.is_empty()
+        && phrase
+            .split(' ')
+            .all(|word| !word.is_empty() && word.chars().all(|c| is_atext(c, EmailMode::Rfc)));
+    if is_atoms {
+        return write!(f, "{}", phrase);
+    }
+    write!(f, "\"")?;
+    for c in phrase.chars() {
+        if c == '"' || c == '\\' {
+            write!(f, "\\")?;
+        }
+        write!(f, "{}", c)?;
+    }
+    write!(f, "\"")
+}
+
+/// Reads mailboxes and groups from a slice of characters, so that positions
+/// in errors count characters like the rest of [`EmailError`].
+struct Cursor<'a> {
+    chars: &'a [char],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(chars: &'a [char]) -> Self {
+        Self { chars, pos: 0 }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn error(&self) -> EmailError {
+        EmailError::InvalidAddressList { position: self.pos }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), EmailError> {
+        if self.peek() != Some(c) {
+            return Err(self.error());
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn end(&mut self) -> Result<(), EmailError> {
+        self.skip_cfws()?;
+        match self.peek() {
+            None => Ok(()),
+            Some(_) => Err(self.error()),
+        }
+    }
+
+    /// Skips whitespace and (possibly nested) comments.
+    fn skip_cfws(&mut self) -> Result<(), EmailError> {
+        loop {
+            match self.peek() {
+                Some(c) if c.is_whitespace() => self.pos += 1,
+                Some('(') => self.skip_comment()?,
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    fn skip_comment(&mut self) -> Result<(), EmailError> {
+        let start = self.pos;
+        let mut depth = 0;
+        while let Some(c) = self.peek() {
+            self.pos += 1;
+            match c {
+                '\\' => self.pos += 1,
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                _ => {}
+            }
+            if depth == 0 {
+                return Ok(());
+            }
+        }
+        Err(EmailError::InvalidAddressList { position: start })
+    }
+
+    /// Reads a quoted string, returning its unescaped contents.
+    fn quoted_string(&mut self) -> Result<String, EmailError> {
+        let start = self.pos;
+        self.expect('"')?;
+        let mut text = String::new();
+        while let Some(c) = self.peek() {
+            self.pos += 1;
+            match c {
+                '"' => return Ok(text),
+                '\\' => match self.peek() {
+                    Some(escaped) => {
+                        text.push(escaped);
+                        self.pos += 1;
+                    }
+                    None => break,
+                },
+                c if c.is_control() && c != '\t' => {
+                    return Err(EmailError::InvalidDisplayName {
+                        position: self.pos - 1,
+                    })
+                }
+                c => text.push(c),
+            }
+        }
+        Err(EmailError::InvalidAddressList { position: start })
+    }
+
+    /// Reads a display name of atoms and quoted strings, joined by single
+    /// spaces. Dots are accepted between words, as in `John Q. Public`.
+    fn phrase(&mut self) -> Result<Option<String>, EmailError> {
+        let mut words = Vec::new();
+        loop {
+            self.skip_cfws()?;
+            match self.peek() {
+                Some('"') => words.push(self.quoted_string()?),
+                Some(c) if c == '.' || is_atext(c, EmailMode::Rfc) => {
+                    let start = self.pos;
+                    while self
+                        .peek()
+                        .is_some_and(|c| c == '.' || is_atext(c, EmailMode::Rfc))
+                    {
+                        self.pos += 1;
+                    }
+                    words.push(self.chars[start..self.pos].iter().collect());
+                }
+                _ => break,
+            }
+        }
+        Ok((!words.is_empty()).then(|| words.join(" ")))
+    }
+
+    /// Reads an address up to the next separator and validates it.
+    fn addr_spec(&mut self) -> Result<Email, EmailError> {
+        self.skip_cfws()?;
+        let start = self.pos;
+        let mut quoted = false;
+        while let Some(c) = self.peek() {
+            if quoted {
+                match c {
+                    '\\' => self.pos += 1,
+                    '"' => quoted = false,
+                    _ => {}
+                }
+            } else if c == '"' {
+                quoted = true;
+            } else if matches!(c, ',' | ';' | '<' | '>' | '(') || c.is_whitespace() {
+                break;
+            }
+            self.pos += 1;
+        }
+        if quoted {
+            return Err(EmailError::InvalidAddressList { position: start });
+        }
+
+        let email: String = self.chars[start..self.pos].iter().collect();
+        match parser::validate(&email, EmailMode::Rfc) {
+            Ok(()) => Ok(Email(email)),
+            Err(reason) => Err(EmailError::InvalidMailbox {
+                position: start,
+                reason: Box::new(reason),
+            }),
+        }
+    }
+
+    fn angle_addr(&mut self) -> Result<Email, EmailError> {
+        self.expect('<')?;
+        let email = self.addr_spec()?;
+        self.skip_cfws()?;
+        self.expect('>')?;
+        Ok(email)
+    }
+
+    fn mailbox(&mut self) -> Result<Mailbox, EmailError> {
+        self.skip_cfws()?;
+        let start = self.pos;
+        if self.peek() == Some('<') {
+            let email = self.angle_addr()?;
+            return Ok(Mailbox { name: None, email });
+        }
+        if let Some(name) = self.phrase()? {
+            if self.peek() == Some('<') {
+                let email = self.angle_addr()?;
+                return Ok(Mailbox {
+                    name: Some(name),
+                    email,
+                });
+            }
+        }
+        // Not a display name after all, but the start of a bare address.
+        self.pos = start;
+        let email = self.addr_spec()?;
+        Ok(Mailbox { name: None, email })
+    }
+
+    fn address(&mut self) -> Result<Address, EmailError> {
+        self.skip_cfws()?;
+        let start = self.pos;
+        if let Some(name) = self.phrase()? {
+            if self.peek() == Some(':') {
+                self.pos += 1;
+                let members = self.group_members()?;
+                return Ok(Address::Group(Group { name, members }));
+            }
+        }
+        self.pos = start;
+        Ok(Address::Mailbox(self.mailbox()?))
+    }
+
+    fn group_members(&mut self) -> Result<Vec<Mailbox>, EmailError> {
+        let mut members = Vec::new();
+        loop {
+            self.skip_cfws()?;
+            match self.peek() {
+                Some(';') => {
+                    self.pos += 1;
+                    return Ok(members);
+                }
+                Some(',') => self.pos += 1,
+                Some(_) => {
+                    members.push(self.mailbox()?);
+                    self.skip_cfws()?;
+                    match self.peek() {
+                        Some(',') => self.pos += 1,
+                        Some(';') => {}
+                        _ => return Err(self.error()),
+                    }
+                }
+                None => return Err(self.error()),
+            }
+        }
+    }
+
+    fn address_list(&mut self) -> Result<Vec<Address>, EmailError> {
+        let mut addresses = Vec::new();
+        loop {
+            self.skip_cfws()?;
+            match self.peek() {
+                None => return Ok(addresses),
+                Some(',') => self.pos += 1,
+                Some(_) => {
+                    addresses.push(self.address()?);
+                    self.skip_cfws()?;
+                    match self.peek() {
+                        None => return Ok(addresses),
+                        Some(',') => self.pos += 1,
+                        Some(_) => return Err(self.error()),
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mailbox(name: Option<&str>, email: &str) -> Mailbox {
+        Mailbox::new(name, Email::parse(email).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_parse_mailbox() {
+        assert_eq!(
+            Mailbox::parse("jane@example.com"),
+            Ok(mailbox(None, "jane@example.com"))
+        );
+        assert_eq!(
+            Mailbox::parse("  <jane@example.com> "),
+            Ok(mailbox(None, "jane@example.com"))
+        );
+        assert_eq!(
+            Mailbox::parse("John Q. Public <john@example.com>"),
+            Ok(mailbox(Some("John Q. Public"), "john@example.com"))
+        );
+        assert_eq!(
+            Mailbox::parse("\"Doe, \\\"JD\\\" John\" (work) <john@example.com>"),
+            Ok(mailbox(Some("Doe, \"JD\" John"), "john@example.com"))
+        );
+        assert_eq!(
+            Mailbox::parse("jane@example.com (Jane Doe)"),
+            Ok(mailbox(None, "jane@example.com"))
+        );
+        assert_eq!(
+            Mailbox::parse("José Núñez <jose@example.com>"),
+            Ok(mailbox(Some("José Núñez"), "jose@example.com"))
+        );
+        assert_eq!(
+            Mailbox::parse("\"a b\"@example.com"),
+            Ok(mailbox(None, "\"a b\"@example.com"))
+        );
+    }
+
+    #[test]
+    fn test_parse_mailbox_errors() {
+        assert_eq!(
+            Mailbox::parse("Jane <jane@example.com"),
+            Err(TypeError::Email(EmailError::InvalidAddressList {
+                position: 22
+            }))
+        );
+        assert_eq!(
+            Mailbox::parse("\"Jane <jane@example.com>"),
+            Err(TypeError::Email(EmailError::InvalidAddressList {
+                position: 0
+            }))
+        );
+        assert_eq!(
+            Mailbox::parse("Jane <jane@@example.com>"),
+            Err(TypeError::Email(EmailError::InvalidMailbox {
+                position: 6,
+                reason: Box::new(EmailError::InvalidDomain { position: 5 }),
+            }))
+        );
+        assert_eq!(
+            Mailbox::parse("a@example.com, b@example.com"),
+            Err(TypeError::Email(EmailError::InvalidAddressList {
+                position: 13
+            }))
+        );
+    }
+
+    #[test]
+    fn test_display_round_trips() {
+        let mailboxes = [
+            mailbox(None, "jane@example.com"),
+            mailbox(Some("Jane Doe"), "jane@example.com"),
+            mailbox(Some("Doe, John"), "john@example.com"),
+            mailbox(Some(" padded  name "), "john@example.com"),
+            mailbox(Some("back\\slash \"quote\""), "john@example.com"),
+            mailbox(Some("J. Doe"), "john@example.com"),
+            mailbox(Some(""), "john@example.com"),
+            mailbox(Some("José"), "jose@example.com"),
+        ];
+        for mailbox in mailboxes {
+            assert_eq!(Mailbox::parse(mailbox.to_string()), Ok(mailbox));
+        }
+        assert_eq!(
+            mailbox(Some("Doe, John"), "john@example.com").to_string(),
+            "\"Doe, John\" <john@example.com>"
+        );
+    }
+
+    #[test]
+    fn test_parse_address_list() {
+        let list = AddressList::parse(
+            "\"Doe, John\" <john@example.com>, jane@example.com,, \
+             Team: a@example.com, B <b@example.com>;, Empty:;,",
+        )
+        .unwrap();
+        assert_eq!(
+            list.addresses(),
+            [
+                Address::Mailbox(mailbox(Some("Doe, John"), "john@example.com")),
+                Address::Mailbox(mailbox(None, "jane@example.com")),
+                Address::Group(
+                    Group::new(
+                        "Team",
+                        vec![
+                            mailbox(None, "a@example.com"),
+                            mailbox(Some("B"), "b@example.com"),
+                        ]
+                    )
+                    .unwrap()
+                ),
+                Address::Group(Group::new("Empty", Vec::new()).unwrap()),
+            ]
+        );
+        assert_eq!(list.mailboxes().count(), 4);
+        assert_eq!(
+            list.to_string(),
+            "\"Doe, John\" <john@example.com>, jane@example.com, \
+             Team: a@example.com, B <b@example.com>;, Empty:;"
+        );
+        assert_eq!(AddressList::parse(list.to_string()), Ok(list));
+        assert!(AddressList::parse("  ").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_address_list_errors() {
+        assert_eq!(
+            AddressList::parse("a@example.com, b@"),
+            Err(TypeError::Email(EmailError::InvalidMailbox {
+                position: 15,
+                reason: Box::new(EmailError::EmptyDomain),
+            }))
+        );
+        assert_eq!(
+            AddressList::parse("Team: a@example.com"),
+            Err(TypeError::Email(EmailError::InvalidAddressList {
+                position: 19
+            }))
+        );
+        assert_eq!(
+            AddressList::parse("a@example.com (unclosed"),
+            Err(TypeError::Email(EmailError::InvalidAddressList {
+                position: 14
+            }))
+        );
+    }
+
+    #[test]
+    fn test_display_name_rejects_control_characters() {
+        let email = Email::parse("jane@example.com").unwrap();
+        assert_eq!(
+            Mailbox::new(Some("Jane\r\nBcc: x@example.com"), email),
+            Err(TypeError::Email(EmailError::InvalidDisplayName {
+                position: 4
+            }))
+        );
+        assert_eq!(
+            Mailbox::parse("\"Jane\r\n\" <jane@example.com>"),
+            Err(TypeError::Email(EmailError::InvalidDisplayName {
+                position: 5
+            }))
+        );
+    }
+
+    #[test]
+    fn test_serde() {
+        let list = AddressList::parse("Jane Doe <jane@example.com>").unwrap();
+        let json = serde_json::to_string(&list).unwrap();
+        assert_eq!(json, "\"Jane Doe <jane@example.com>\"");
+        assert_eq!(serde_json::from_str::<AddressList>(&json).unwrap(), list);
+    }
+}
@@ -0,0 +1,462 @@
+use crate::error::EmailError;
+use serde::{Deserialize, Serialize};
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+/// Maximum length of the local part in octets (RFC 5321, section 4.5.3.1.1).
+const MAX_LOCAL_PART_LEN: usize = 64;
+/// Maximum length of a domain label in octets (RFC 1035, section 2.3.4).
+const MAX_LABEL_LEN: usize = 63;
+/// Maximum length of a domain in octets (RFC 5321, section 4.5.3.1.2).
+const MAX_DOMAIN_LEN: usize = 255;
+/// Maximum length of an address in octets, so that it fits the 256-octet
+/// reverse-path with its angle brackets (RFC 5321, section 4.5.3.1.3).
+const MAX_ADDRESS_LEN: usize = 254;
+
+/// Prefix of the ASCII-compatible encoding of an internationalized label.
+const ACE_PREFIX: &str = "xn--";
+
+/// Characters allowed in an unquoted local part besides ASCII letters and digits.
+const ATEXT_SYMBOLS: &str = "!#$%&'*+-/=?^_`{|}~";
+
+/// How strictly [`Email::parse_with`](crate::Email::parse_with) follows the
+/// address grammar.
+///
+/// # Example
+///
+/// ```
+/// use custom_type::{Email, EmailMode};
+///
+/// assert!(Email::parse_with(EmailMode::Rfc, "\"john doe\"@example.com").is_ok());
+/// assert!(Email::parse_with(EmailMode::Practical, "\"john doe\"@example.com").is_err());
+/// assert!(Email::parse_with(EmailMode::Html5, "john..doe@example.com").is_ok());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum EmailMode {
+    /// The RFC 5321 mailbox grammar with the RFC 6531 UTF-8 extension:
+    /// dot-atom or quoted-string local parts, host names or IPv4/IPv6 address
+    /// literals, and the RFC length limits.
+    #[default]
+    Rfc,
+    /// The rules browsers apply to `<input type="email">`: any run of ASCII
+    /// atext and dots as the local part, a host name as the domain, no length
+    /// limits.
+    Html5,
+    /// What people actually type: a dot-atom local part, which may contain
+    /// UTF-8, and a host name with at least two labels and a non-numeric
+    /// top-level label.
+    Practical,
+}
+
+/// Validates `email` in `mode`, reporting the position of the first offending
+/// character. Positions count characters, not bytes.
+pub(crate) fn validate(email: &str, mode: EmailMode) -> Result<(), EmailError> {
+    if !email.contains('@') {
+        return Err(EmailError::MissingAt);
+    }
+    let chars: Vec<char> = email.chars().collect();
+
+    let at = local_part_end(&chars, mode)?;
+    let local_part: String = chars[..at].iter().collect();
+    if mode != EmailMode::Html5 && local_part.len() > MAX_LOCAL_PART_LEN {
+        return Err(EmailError::LocalPartTooLong {
+            max: MAX_LOCAL_PART_LEN,
+            actual: local_part.len(),
+        });
+    }
+
+    validate_domain(&chars[at + 1..], at + 1, mode)?;
+
+    let domain: String = chars[at + 1..].iter().collect();
+    let len = local_part.len() + 1 + to_ascii(&domain).len();
+    if mode != EmailMode::Html5 && len > MAX_ADDRESS_LEN {
+        return Err(EmailError::TooLong {
+            max: MAX_ADDRESS_LEN,
+            actual: len,
+        });
+    }
+    Ok(())
+}
+
+/// Returns the lowercase ASCII-compatible form of a validated domain, with
+/// internationalized labels encoded as punycode.
+pub(crate) fn to_ascii(domain: &str) -> String {
+    if domain.is_ascii() {
+        domain.to_ascii_lowercase()
+    } else {
+        idna::domain_to_ascii(domain).unwrap_or_else(|_| domain.to_lowercase())
+    }
+}
+
+/// Returns the lowercase Unicode form of a validated domain, with punycode
+/// labels decoded.
+pub(crate) fn to_unicode(domain: &str) -> String {
+    if domain.starts_with('[') {
+        return domain.to_lowercase();
+    }
+    match idna::domain_to_unicode(domain) {
+        (unicode, Ok(())) => unicode,
+        (_, Err(_)) => domain.to_lowercase(),
+    }
+}
+
+/// Returns the position of the `@` ending the local part.
+fn local_part_end(chars: &[char], mode: EmailMode) -> Result<usize, EmailError> {
+    match chars.first() {
+        Some('@') => Err(EmailError::EmptyLocalPart),
+        Some('"') if mode == EmailMode::Rfc => quoted_string_end(chars),
+        _ => dot_string_end(chars, mode),
+    }
+}
+
+/// Scans a dot-atom local part, or any run of atext and dots in HTML5 mode.
+fn dot_string_end(chars: &[char], mode: EmailMode) -> Result<usize, EmailError> {
+    let strict_dots = mode != EmailMode::Html5;
+    let mut after_dot = true;
+    for (position, c) in chars.iter().enumerate() {
+        match c {
+            '@' if strict_dots && after_dot => {
+                return Err(EmailError::InvalidLocalPart {
+                    position: position - 1,
+                })
+            }
+            '@' => return Ok(position),
+            '.' if strict_dots && after_dot => {
+                return Err(EmailError::InvalidLocalPart { position })
+            }
+            '.' => after_dot = true,
+            c if is_atext(*c, mode) => after_dot = false,
+            _ => return Err(EmailError::InvalidLocalPart { position }),
+        }
+    }
+    Err(EmailError::MissingAt)
+}
+
+/// Scans an RFC 5321 quoted-string local part, which must be followed by `@`.
+fn quoted_string_end(chars: &[char]) -> Result<usize, EmailError> {
+    let mut position = 1;
+    loop {
+        match chars.get(position) {
+            None => return Err(EmailError::InvalidLocalPart { position: 0 }),
+            Some('"') => break,
+            Some('\\') => match chars.get(position + 1) {
+                Some(c) if (' '..='~').contains(c) => position += 2,
+                _ => {
+                    return Err(EmailError::InvalidLocalPart {
+                        position: position + 1,
+                    })
+                }
+            },
+            Some(c) if (' '..='~').contains(c) || is_utf8_non_ascii(*c) => position += 1,
+            Some(_) => return Err(EmailError::InvalidLocalPart { position }),
+        }
+    }
+    match chars.get(position + 1) {
+        Some('@') => Ok(position + 1),
+        None => Err(EmailError::MissingAt),
+        Some(_) => Err(EmailError::InvalidLocalPart {
+            position: position + 1,
+        }),
+    }
+}
+
+/// Validates the domain `chars`, which start at `offset` in the address.
+fn validate_domain(chars: &[char], offset: usize, mode: EmailMode) -> Result<(), EmailError> {
+    if chars.is_empty() {
+        return Err(EmailError::EmptyDomain);
+    }
+    let domain: String = chars.iter().collect();
+    if chars[0] == '[' {
+        if mode != EmailMode::Rfc {
+            return Err(EmailError::InvalidDomain { position: offset });
+        }
+        return validate_address_literal(chars, offset);
+    }
+
+    let is_idn = !domain.is_ascii()
+        || domain.split('.').any(|label| {
+            label
+                .get(..ACE_PREFIX.len())
+                .is_some_and(|prefix| prefix.eq_ignore_ascii_case(ACE_PREFIX))
+        });
+    if !is_idn {
+        return validate_host_name(chars, offset, mode);
+    }
+
+    // Positions inside the ASCII form do not map back onto the input, so
+    // errors in an internationalized domain point at its start.
+    let invalid = EmailError::InvalidDomain { position: offset };
+    let ascii = idna::domain_to_ascii_strict(&domain).map_err(|_| invalid.clone())?;
+    let ascii: Vec<char> = ascii.chars().collect();
+    validate_host_name(&ascii, offset, mode).map_err(|_| invalid.clone())?;
+    match idna::domain_to_unicode(&ascii.iter().collect::<String>()) {
+        (_, Ok(())) => Ok(()),
+        (_, Err(_)) => Err(invalid),
+    }
+}
+
+/// Validates an ASCII host name whose `chars` start at `offset` in the address.
+fn validate_host_name(chars: &[char], offset: usize, mode: EmailMode) -> Result<(), EmailError> {
+    let mut position = offset;
+    let mut labels = Vec::new();
+    for label in chars.split(|c| *c == '.') {
+        validate_label(label, position)?;
+        labels.push(label);
+        position += label.len() + 1;
+    }
+
+    if mode == EmailMode::Practical {
+        let tld = labels[labels.len() - 1];
+        if labels.len() < 2 {
+            return Err(EmailError::InvalidDomain {
+                position: offset + chars.len(),
+            });
+        }
+        if tld.iter().all(char::is_ascii_digit) {
+            return Err(EmailError::InvalidDomain {
+                position: offset + chars.len() - tld.len(),
+            });
+        }
+    }
+
+    let len = chars.len();
+    if mode != EmailMode::Html5 && len > MAX_DOMAIN_LEN {
+        return Err(EmailError::DomainTooLong {
+            max: MAX_DOMAIN_LEN,
+            actual: len,
+        });
+    }
+    Ok(())
+}
+
+/// Validates a host name label starting at `position`: letters, digits and
+/// hyphens, neither starting nor ending with a hyphen.
+fn validate_label(label: &[char], position: usize) -> Result<(), EmailError> {
+    if label.is_empty() || label[0] == '-' {
+        return Err(EmailError::InvalidDomain { position });
+    }
+    if let Some(index) = label
+        .iter()
+        .position(|c| !c.is_ascii_alphanumeric() && *c != '-')
+    {
+        return Err(EmailError::InvalidDomain {
+            position: position + index,
+        });
+    }
+    if label[label.len() - 1] == '-' {
+        return Err(EmailError::InvalidDomain {
+            position: position + label.len() - 1,
+        });
+    }
+    if label.len() > MAX_LABEL_LEN {
+        return Err(EmailError::LabelTooLong {
+            position,
+            max: MAX_LABEL_LEN,
+            actual: label.len(),
+        });
+    }
+    Ok(())
+}
+
+/// Validates an address literal such as `[192.0.2.1]` or `[IPv6:2001:db8::1]`.
+fn validate_address_literal(chars: &[char], offset: usize) -> Result<(), EmailError> {
+    let close = chars
+        .iter()
+        .position(|c| *c == ']')
+        .ok_or(EmailError::InvalidDomain {
+            position: offset + chars.len(),
+        })?;
+    if close != chars.len() - 1 {
+        return Err(EmailError::InvalidDomain {
+            position: offset + close + 1,
+        });
+    }
+
+    let literal: String = chars[1..close].iter().collect();
+    let valid = match literal.get(..5) {
+        Some(tag) if tag.eq_ignore_ascii_case("ipv6:") => literal[5..].parse::<Ipv6Addr>().is_ok(),
+        _ => literal.parse::<Ipv4Addr>().is_ok(),
+    };
+    if !valid {
+        return Err(EmailError::InvalidDomain {
+            position: offset + 1,
+        });
+    }
+    Ok(())
+}
+
+pub(crate) fn is_atext(c: char, mode: EmailMode) -> bool {
+    c.is_ascii_alphanumeric()
+        || ATEXT_SYMBOLS.contains(c)
+        || (mode != EmailMode::Html5 && is_utf8_non_ascii(c))
+}
+
+/// Returns `true` for the non-ASCII characters RFC 6532 allows in atext and
+/// qtext, leaving out control and whitespace characters.
+fn is_utf8_non_ascii(c: char) -> bool {
+    !c.is_ascii() && !c.is_control() && !c.is_whitespace()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rfc_mode() {
+        let valid = [
+            "simple@example.com",
+            "very.common@example.com",
+            "x@example.com",
+            "user@localhost",
+            "\"john doe\"@example.com",
+            "\"john..doe\"@example.com",
+            "\"a\\\"b\"@example.com",
+            "\"\"@example.com",
+            "\"a@b\"@example.com",
+            "user@[192.0.2.1]",
+            "user@[ipv6:2001:db8::1]",
+            "!#$%&'*+-/=?^_`{|}~@example.com",
+        ];
+        for email in valid {
+            assert!(validate(email, EmailMode::Rfc).is_ok(), "{}", email);
+        }
+    }
+
+    #[test]
+    fn test_local_part_errors() {
+        assert_eq!(
+            validate(".user@example.com", EmailMode::Rfc),
+            Err(EmailError::InvalidLocalPart { position: 0 })
+        );
+        assert_eq!(
+            validate("user.@example.com", EmailMode::Rfc),
+            Err(EmailError::InvalidLocalPart { position: 4 })
+        );
+        assert_eq!(
+            validate("a..b@example.com", EmailMode::Rfc),
+            Err(EmailError::InvalidLocalPart { position: 2 })
+        );
+        assert_eq!(
+            validate("\"unterminated@example.com", EmailMode::Rfc),
+            Err(EmailError::InvalidLocalPart { position: 0 })
+        );
+        assert_eq!(
+            validate("\"quoted\"x@example.com", EmailMode::Rfc),
+            Err(EmailError::InvalidLocalPart { position: 8 })
+        );
+        assert_eq!(
+            validate(&format!("{}@example.com", "a".repeat(65)), EmailMode::Rfc),
+            Err(EmailError::LocalPartTooLong {
+                max: 64,
+                actual: 65
+            })
+        );
+    }
+
+    #[test]
+    fn test_domain_errors() {
+        assert_eq!(
+            validate("user@-example.com", EmailMode::Rfc),
+            Err(EmailError::InvalidDomain { position: 5 })
+        );
+        assert_eq!(
+            validate("user@example-.com", EmailMode::Rfc),
+            Err(EmailError::InvalidDomain { position: 12 })
+        );
+        assert_eq!(
+            validate("user@example.com.", EmailMode::Rfc),
+            Err(EmailError::InvalidDomain { position: 17 })
+        );
+        assert_eq!(
+            validate(&format!("user@{}.com", "a".repeat(64)), EmailMode::Rfc),
+            Err(EmailError::LabelTooLong {
+                position: 5,
+                max: 63,
+                actual: 64
+            })
+        );
+        assert_eq!(
+            validate("user@[300.0.2.1]", EmailMode::Rfc),
+            Err(EmailError::InvalidDomain { position: 6 })
+        );
+        assert_eq!(
+            validate("user@[192.0.2.1", EmailMode::Rfc),
+            Err(EmailError::InvalidDomain { position: 15 })
+        );
+    }
+
+    #[test]
+    fn test_total_length() {
+        let label = "a".repeat(63);
+        let email = format!(
+            "{}@{}.{}.{}.{}",
+            "a".repeat(64),
+            label,
+            label,
+            label,
+            "a".repeat(5)
+        );
+        assert_eq!(
+            validate(&email, EmailMode::Rfc),
+            Err(EmailError::TooLong {
+                max: 254,
+                actual: 262
+            })
+        );
+        assert!(validate(&email, EmailMode::Html5).is_ok());
+    }
+
+    #[test]
+    fn test_html5_mode() {
+        assert!(validate("a..b.@example.com", EmailMode::Html5).is_ok());
+        assert!(validate("user@localhost", EmailMode::Html5).is_ok());
+        assert_eq!(
+            validate("\"john doe\"@example.com", EmailMode::Html5),
+            Err(EmailError::InvalidLocalPart { position: 0 })
+        );
+        assert_eq!(
+            validate("user@[192.0.2.1]", EmailMode::Html5),
+            Err(EmailError::InvalidDomain { position: 5 })
+        );
+    }
+
+    #[test]
+    fn test_practical_mode() {
+        assert!(validate("user@example.com", EmailMode::Practical).is_ok());
+        assert_eq!(
+            validate("user@localhost", EmailMode::Practical),
+            Err(EmailError::InvalidDomain { position: 14 })
+        );
+        assert_eq!(
+            validate("user@192.0.2.1", EmailMode::Practical),
+            Err(EmailError::InvalidDomain { position: 13 })
+        );
+        assert_eq!(
+            validate("a..b@example.com", EmailMode::Practical),
+            Err(EmailError::InvalidLocalPart { position: 2 })
+        );
+    }
+    #[test]
+    fn test_internationalized() {
+        assert!(validate("josé@exämple.de", EmailMode::Rfc).is_ok());
+        assert!(validate("用户@例子.广告", EmailMode::Practical).is_ok());
+        assert!(validate("\"josé d\"@example.com", EmailMode::Rfc).is_ok());
+        assert!(validate("user@XN--EXMPLE-CUA.de", EmailMode::Rfc).is_ok());
+        assert_eq!(to_ascii("Exämple.DE"), "xn--exmple-cua.de");
+        assert_eq!(to_unicode("XN--EXMPLE-CUA.de"), "exämple.de");
+        assert_eq!(to_unicode("[IPv6:2001:DB8::1]"), "[ipv6:2001:db8::1]");
+
+        assert_eq!(
+            validate("josé@example.com", EmailMode::Html5),
+            Err(EmailError::InvalidLocalPart { position: 3 })
+        );
+        assert!(validate("user@exämple.de", EmailMode::Html5).is_ok());
+        assert_eq!(
+            validate("user@xn--invalid-.de", EmailMode::Rfc),
+            Err(EmailError::InvalidDomain { position: 5 })
+        );
+        assert_eq!(
+            validate("us\u{a0}er@example.com", EmailMode::Rfc),
+            Err(EmailError::InvalidLocalPart { position: 2 })
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use crate::email::disposable::{self, DisposableDomains};
+use crate::email::{parser, EmailMode};
+use crate::error::EmailError;
+use std::sync::Arc;
+
+/// ### EmailPolicy : Configurable Email Rules
+/// Chooses the address grammar and the extra checks an address must pass.
+/// Pass it to [`Email::parse_with_policy`](crate::Email::parse_with_policy).
+///
+/// # Example
+///
+/// ```
+/// use custom_type::{DisposableDomains, Email, EmailMode, EmailPolicy};
+///
+/// let policy = EmailPolicy::new()
+///     .mode(EmailMode::Practical)
+///     .reject_disposable();
+///
+/// assert!(Email::parse_with_policy(&policy, "jane@example.com").is_ok());
+/// assert!(Email::parse_with_policy(&policy, "jane@mailinator.com").is_err());
+///
+/// let mut domains = DisposableDomains::new();
+/// domains.block("throwaway.example");
+/// let policy = EmailPolicy::new().reject_disposable_in(domains);
+/// assert!(Email::parse_with_policy(&policy, "jane@mailinator.com").is_ok());
+/// assert!(Email::parse_with_policy(&policy, "jane@throwaway.example").is_err());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct EmailPolicy {
+    mode: EmailMode,
+    disposable: Option<Disposable>,
+}
+
+/// The disposable domain list a policy checks: the built-in list is
+/// borrowed, so only custom lists are stored.
+#[derive(Debug, Clone)]
+enum Disposable {
+    Builtin(&'static DisposableDomains),
+    Custom(Arc<DisposableDomains>),
+}
+
+impl Disposable {
+    fn domains(&self) -> &DisposableDomains {
+        match self {
+            Disposable::Builtin(domains) => domains,
+            Disposable::Custom(domains) => domains,
+        }
+    }
+}
+
+impl EmailPolicy {
+    /// Creates a policy that follows [`EmailMode::Rfc`] with no extra checks.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Follows the grammar of `mode`.
+    pub fn mode(mut self, mode: EmailMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Rejects addresses at domains in the list built into the crate.
+    pub fn reject_disposable(mut self) -> Self {
+        self.disposable = Some(Disposable::Builtin(disposable::builtin()));
+        self
+    }
+
+    /// Rejects addresses at domains in `domains`, e.g. a list extended with
+    /// [`DisposableDomains::load_blocklist`].
+    pub fn reject_disposable_in(mut self, domains: DisposableDomains) -> Self {
+        self.disposable = Some(Disposable::Custom(Arc::new(domains)));
+        self
+    }
+
+    /// Validates `email` against the grammar and every configured check.
+    pub(crate) fn check(&self, email: &str) -> Result<(), EmailError> {
+        parser::validate(email, self.mode)?;
+
+        let (_, domain) = email
+            .rsplit_once('@')
+            .expect("a validated email contains '@'");
+        if let Some(disposable) = &self.disposable {
+            if disposable.domains().contains(domain) {
+                return Err(EmailError::DisposableDomain {
+                    domain: domain.to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
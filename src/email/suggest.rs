@@ -0,0 +1,285 @@
+use crate::distance::damerau_levenshtein;
+use crate::email::disposable::normalize;
+use std::sync::OnceLock;
+
+/// Widely used mail domains, most popular first.
+const POPULAR_DOMAINS: &str = include_str!("data/popular_domains.txt");
+
+/// Common generic top-level domains and public suffixes, then every
+/// ISO 3166 country code top-level domain.
+const TOP_LEVEL_DOMAINS: &str = include_str!("data/top_level_domains.txt");
+
+/// ### DomainSuggester : "Did You Mean" for Email Domains
+/// Suggests a correction for a mistyped domain. A domain within
+/// `max_distance` edits of a popular domain is corrected to it; otherwise a
+/// top-level domain within `max_tld_distance` edits of a known one is
+/// corrected, so `example.cmo` becomes `example.com`. Swapping two adjacent
+/// characters counts as one edit, and ties go to the domain listed first.
+///
+/// Short names are easily mistaken for one another, so a popular domain
+/// whose name has at most 5 characters, like `yahoo.com`, is suggested for
+/// domains at most 1 edit away, and one of at most 3 characters, like
+/// `aol.com`, only for domains with a mistyped top-level domain. A domain
+/// that already ends in a known top-level domain is only corrected to a
+/// popular domain 1 edit away with the same top-level domain, so `hp.com`
+/// and `gmx.at` are left alone.
+///
+/// The defaults are the popular domains and top-level domains built into the
+/// crate, with a `max_distance` of 2 and a `max_tld_distance` of 1.
+///
+/// # Example
+///
+/// ```
+/// use custom_type::DomainSuggester;
+///
+/// let suggester = DomainSuggester::new()
+///     .add_domains(["example-corp.com"])
+///     .max_distance(1);
+///
+/// assert_eq!(suggester.suggest("gmial.com").as_deref(), Some("gmail.com"));
+/// assert_eq!(suggester.suggest("exmaple-corp.com").as_deref(), Some("example-corp.com"));
+/// assert_eq!(suggester.suggest("example.cmo").as_deref(), Some("example.com"));
+/// assert_eq!(suggester.suggest("gmail.com"), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DomainSuggester {
+    domains: Vec<String>,
+    top_level_domains: Vec<String>,
+    max_distance: usize,
+    max_tld_distance: usize,
+}
+
+impl Default for DomainSuggester {
+    fn default() -> Self {
+        Self {
+            domains: POPULAR_DOMAINS.lines().map(str::to_string).collect(),
+            top_level_domains: TOP_LEVEL_DOMAINS.lines().map(str::to_string).collect(),
+            max_distance: 2,
+            max_tld_distance: 1,
+        }
+    }
+}
+
+impl DomainSuggester {
+    /// Creates a suggester with the lists and thresholds built into the crate.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces the popular domains, most popular first.
+    pub fn domains(mut self, domains: impl IntoIterator<Item = impl ToString>) -> Self {
+        self.domains = domains
+            .into_iter()
+            .map(|d| normalize(&d.to_string()))
+            .collect();
+        self
+    }
+
+    /// Adds popular domains after the existing ones.
+    pub fn add_domains(mut self, domains: impl IntoIterator<Item = impl ToString>) -> Self {
+        self.domains
+            .extend(domains.into_iter().map(|d| normalize(&d.to_string())));
+        self
+    }
+
+    /// Replaces the known top-level domains, most common first.
+    pub fn top_level_domains(mut self, tlds: impl IntoIterator<Item = impl ToString>) -> Self {
+        self.top_level_domains = tlds
+            .into_iter()
+            .map(|tld| normalize(&tld.to_string()))
+            .collect();
+        self
+    }
+
+    /// Suggests a popular domain at most `max` edits away, or fewer for
+    /// popular domains with short names.
+    pub fn max_distance(mut self, max: usize) -> Self {
+        self.max_distance = max;
+        self
+    }
+
+    /// Suggests a top-level domain at most `max` edits away.
+    pub fn max_tld_distance(mut self, max: usize) -> Self {
+        self.max_tld_distance = max;
+        self
+    }
+
+    /// Returns the corrected form of `domain`, or `None` if it looks right
+    /// or nothing is close enough.
+    pub fn suggest(&self, domain: &str) -> Option<String> {
+        if domain.starts_with('[') {
+            return None;
+        }
+        let domain = normalize(domain);
+        if self.domains.contains(&domain) {
+            return None;
+        }
+
+        let labels: Vec<&str> = domain.split('.').collect();
+        let tld = self.known_tld(&labels);
+        let max = if tld.is_some() { 1 } else { self.max_distance };
+        let popular = closest(
+            self.domains
+                .iter()
+                .filter(|candidate| {
+                    tld.is_none_or(|tld| {
+                        candidate
+                            .strip_suffix(tld.as_str())
+                            .is_some_and(|name| name.ends_with('.'))
+                    })
+                })
+                .map(|candidate| (damerau_levenshtein(&domain, candidate), candidate))
+                .filter(|(distance, candidate)| *distance <= max_distance_for(candidate)),
+            max,
+        );
+        match (popular, tld) {
+            (Some(popular), _) => Some(popular.clone()),
+            (None, Some(_)) => None,
+            (None, None) => self.suggest_tld(&labels),
+        }
+    }
+
+    /// Returns the longest known top-level domain `labels` end in, if any.
+    fn known_tld(&self, labels: &[&str]) -> Option<&String> {
+        self.top_level_domains
+            .iter()
+            .filter(|tld| {
+                let count = label_count(tld);
+                labels.len() > count && labels[labels.len() - count..].join(".") == **tld
+            })
+            .max_by_key(|tld| label_count(tld))
+    }
+
+    /// Corrects the top-level domain of a domain that does not end in a
+    /// known one.
+    fn suggest_tld(&self, labels: &[&str]) -> Option<String> {
+        let suffix = |count: usize| labels[labels.len() - count..].join(".");
+        let tld = closest(
+            self.top_level_domains
+                .iter()
+                .filter(|tld| labels.len() > label_count(tld))
+                .map(|tld| (damerau_levenshtein(&suffix(label_count(tld)), tld), tld)),
+            self.max_tld_distance,
+        )?;
+        let name = labels[..labels.len() - label_count(tld)].join(".");
+        Some(format!("{}.{}", name, tld))
+    }
+}
+
+/// Returns the number of labels in `domain`.
+fn label_count(domain: &str) -> usize {
+    domain.split('.').count()
+}
+
+/// Returns how many edits away a domain may be to be corrected to
+/// `candidate`: none if its first label has at most 3 characters, 1 if it
+/// has at most 5, and any number otherwise.
+fn max_distance_for(candidate: &str) -> usize {
+    match candidate
+        .split('.')
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .count()
+    {
+        0..=3 => 0,
+        4..=5 => 1,
+        _ => usize::MAX,
+    }
+}
+
+/// Returns the first candidate with the smallest distance, if it is at most
+/// `max`.
+fn closest<'a>(
+    candidates: impl Iterator<Item = (usize, &'a String)>,
+    max: usize,
+) -> Option<&'a String> {
+    let mut best: Option<(usize, &String)> = None;
+    for (distance, candidate) in candidates {
+        if distance <= max && best.is_none_or(|(best, _)| distance < best) {
+            best = Some((distance, candidate));
+        }
+    }
+    best.map(|(_, candidate)| candidate)
+}
+
+/// Returns the suggester with the lists and thresholds built into the crate.
+pub(crate) fn builtin() -> &'static DomainSuggester {
+    static BUILTIN: OnceLock<DomainSuggester> = OnceLock::new();
+    BUILTIN.get_or_init(DomainSuggester::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_suggest_popular_domain() {
+        let suggester = builtin();
+        assert_eq!(suggester.suggest("gmial.com").as_deref(), Some("gmail.com"));
+        assert_eq!(suggester.suggest("GMAIL.CMO").as_deref(), Some("gmail.com"));
+        assert_eq!(suggester.suggest("yaho.com").as_deref(), Some("yahoo.com"));
+        assert_eq!(
+            suggester.suggest("hotmial.con").as_deref(),
+            Some("hotmail.com")
+        );
+        assert_eq!(suggester.suggest("aol.cmo").as_deref(), Some("aol.com"));
+        assert_eq!(
+            suggester.suggest("hotmal.com").as_deref(),
+            Some("hotmail.com")
+        );
+        assert_eq!(suggester.suggest("yahoo.co.uk"), None);
+    }
+
+    #[test]
+    fn test_leave_real_domains_alone() {
+        let suggester = builtin();
+        for domain in [
+            "hp.com", "ge.com", "mail.com", "att.com", "aon.com", "gmx.at",
+        ] {
+            assert_eq!(suggester.suggest(domain), None, "{}", domain);
+        }
+        assert_eq!(suggester.suggest("yahooo.co"), None);
+        assert_eq!(
+            suggester.suggest("yahooo.co.uk").as_deref(),
+            Some("yahoo.co.uk")
+        );
+    }
+
+    #[test]
+    fn test_suggest_top_level_domain() {
+        let suggester = builtin();
+        assert_eq!(
+            suggester.suggest("example.cmo").as_deref(),
+            Some("example.com")
+        );
+        assert_eq!(
+            suggester.suggest("example.nte").as_deref(),
+            Some("example.net")
+        );
+        assert_eq!(
+            suggester.suggest("example.co.ukk").as_deref(),
+            Some("example.co.uk")
+        );
+        assert_eq!(suggester.suggest("example.com"), None);
+        assert_eq!(suggester.suggest("mail.example.de"), None);
+        assert_eq!(suggester.suggest("example.io"), None);
+        assert_eq!(suggester.suggest("example.qwerty"), None);
+        assert_eq!(suggester.suggest("[192.0.2.1]"), None);
+    }
+
+    #[test]
+    fn test_custom_lists_and_thresholds() {
+        let suggester = DomainSuggester::new()
+            .domains(["Acme.example"])
+            .top_level_domains(["test"])
+            .max_distance(1)
+            .max_tld_distance(0);
+        assert_eq!(
+            suggester.suggest("acme.exampel").as_deref(),
+            Some("acme.example")
+        );
+        assert_eq!(suggester.suggest("gmial.com"), None);
+        assert_eq!(suggester.suggest("acme.tset"), None);
+    }
+}
@@ -1,3 +1,5 @@
+use crate::password::{CharClass, ContextField};
+use crate::{CountryCode, NumberType};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -6,38 +8,441 @@ use std::fmt;
 /// # Example
 ///
 /// ```
-/// use custom_type::error::TypeError;
+/// use custom_type::error::{EmailError, TypeError};
+/// use custom_type::Email;
 ///
-/// let error = TypeError::ParseError("Invalid input".to_string());
+/// let error = Email::parse("invalid-email").unwrap_err();
+/// assert_eq!(error, TypeError::Email(EmailError::MissingAt));
 /// println!("{}", error);
 /// ```
 ///
 /// # Features
 ///
-/// - Custom error type `TypeError` for handling parse errors.
+/// - Custom error type `TypeError` with one structured error kind per parsed type.
 /// - Implements `fmt::Display` and `std::error::Error` for `TypeError`.
+///
 /// Enum representing different types of errors.
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TypeError {
-    /// Represents an error that occurs during parsing.
-    ParseError(String),
+    /// Represents an error that occurs while parsing an email address.
+    Email(EmailError),
+    /// Represents an error that occurs while parsing a password.
+    Password(PasswordError),
+    /// Represents an error that occurs while parsing a phone number.
+    Phone(PhoneError),
+    /// Represents an error that occurs while parsing a URL.
+    Url(UrlError),
+    /// Represents an error that occurs while hashing a password or parsing a stored hash.
+    Hash(HashError),
 }
 
 impl fmt::Display for TypeError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            TypeError::ParseError(msg) => write!(f, "{}", msg),
+            TypeError::Email(err) => write!(f, "unable to parse email, {}.", err),
+            TypeError::Password(err) => write!(f, "unable to parse password, {}.", err),
+            TypeError::Phone(err) => write!(f, "unable to parse phone number, {}.", err),
+            TypeError::Url(err) => write!(f, "unable to parse URL, {}.", err),
+            TypeError::Hash(err) => write!(f, "unable to process password hash, {}.", err),
+        }
+    }
+}
+
+impl std::error::Error for TypeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TypeError::Email(err) => Some(err),
+            TypeError::Password(err) => Some(err),
+            TypeError::Phone(err) => Some(err),
+            TypeError::Url(err) => Some(err),
+            TypeError::Hash(err) => Some(err),
+        }
+    }
+}
+
+impl From<EmailError> for TypeError {
+    fn from(err: EmailError) -> Self {
+        TypeError::Email(err)
+    }
+}
+
+impl From<PasswordError> for TypeError {
+    fn from(err: PasswordError) -> Self {
+        TypeError::Password(err)
+    }
+}
+
+impl From<PhoneError> for TypeError {
+    fn from(err: PhoneError) -> Self {
+        TypeError::Phone(err)
+    }
+}
+
+impl From<UrlError> for TypeError {
+    fn from(err: UrlError) -> Self {
+        TypeError::Url(err)
+    }
+}
+
+impl From<HashError> for TypeError {
+    fn from(err: HashError) -> Self {
+        TypeError::Hash(err)
+    }
+}
+
+/// Reasons an email address can be rejected.
+///
+/// Positions are character indices into the input.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EmailError {
+    /// The address does not contain an `@`.
+    MissingAt,
+    /// Nothing precedes the `@`.
+    EmptyLocalPart,
+    /// The local part contains a character that is not allowed.
+    InvalidLocalPart { position: usize },
+    /// Nothing follows the `@`.
+    EmptyDomain,
+    /// The domain contains a disallowed character or an empty label.
+    InvalidDomain { position: usize },
+    /// The local part is longer than 64 octets.
+    LocalPartTooLong { max: usize, actual: usize },
+    /// A domain label is longer than 63 octets.
+    LabelTooLong {
+        position: usize,
+        max: usize,
+        actual: usize,
+    },
+    /// The domain is longer than 255 octets.
+    DomainTooLong { max: usize, actual: usize },
+    /// The address is longer than 254 octets.
+    TooLong { max: usize, actual: usize },
+    /// The domain belongs to a disposable email service.
+    DisposableDomain { domain: String },
+    /// An address list or mailbox is malformed, e.g. an unclosed `<`, quote
+    /// or comment.
+    InvalidAddressList { position: usize },
+    /// The address starting at `position` of an address list is invalid.
+    InvalidMailbox {
+        position: usize,
+        reason: Box<EmailError>,
+    },
+    /// A display name contains a control character, such as a line break.
+    InvalidDisplayName { position: usize },
+}
+
+impl fmt::Display for EmailError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EmailError::MissingAt => write!(f, "missing '@'"),
+            EmailError::EmptyLocalPart => write!(f, "empty local part"),
+            EmailError::InvalidLocalPart { position } => {
+                write!(f, "invalid local part at position {}", position)
+            }
+            EmailError::EmptyDomain => write!(f, "empty domain"),
+            EmailError::InvalidDomain { position } => {
+                write!(f, "invalid domain at position {}", position)
+            }
+            EmailError::LocalPartTooLong { max, actual } => write!(
+                f,
+                "local part must be at most {} octets long, got {}",
+                max, actual
+            ),
+            EmailError::LabelTooLong {
+                position,
+                max,
+                actual,
+            } => write!(
+                f,
+                "domain label at position {} must be at most {} octets long, got {}",
+                position, max, actual
+            ),
+            EmailError::DomainTooLong { max, actual } => write!(
+                f,
+                "domain must be at most {} octets long, got {}",
+                max, actual
+            ),
+            EmailError::TooLong { max, actual } => {
+                write!(f, "must be at most {} octets long, got {}", max, actual)
+            }
+            EmailError::DisposableDomain { domain } => {
+                write!(f, "{} is a disposable email domain", domain)
+            }
+            EmailError::InvalidAddressList { position } => {
+                write!(f, "invalid address list at position {}", position)
+            }
+            EmailError::InvalidMailbox { position, reason } => {
+                write!(f, "invalid address at position {}: {}", position, reason)
+            }
+            EmailError::InvalidDisplayName { position } => {
+                write!(f, "invalid display name at position {}", position)
+            }
         }
     }
 }
 
+impl std::error::Error for EmailError {}
+
+/// Reasons a password can be rejected.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PasswordError {
+    /// The password has fewer characters than the required minimum.
+    TooShort { min: usize, actual: usize },
+    /// The password has more characters than the allowed maximum.
+    TooLong { max: usize, actual: usize },
+    /// The password has fewer characters of a class than required.
+    MissingClass {
+        class: CharClass,
+        min: usize,
+        actual: usize,
+    },
+    /// The password draws from fewer character classes than required.
+    NotEnoughClasses { min: usize, actual: usize },
+    /// The password's estimated strength score is below the required minimum.
+    TooWeak { min: u8, actual: u8 },
+    /// The password contains a forbidden character.
+    ForbiddenCharacter { position: usize, character: char },
+    /// The password does not satisfy the named custom rule.
+    CustomRule(String),
+    /// The password contains a token of the account context, such as the username.
+    ContainsContext { field: ContextField },
+    /// The password is too few edits away from the previous password.
+    TooSimilar { min: usize, actual: usize },
+    /// The password matches one of the account's recent passwords.
+    Reused { last: usize },
+    /// The password appears in a breach corpus this many times.
+    Breached { count: u64 },
+    /// The breach corpus could not be read.
+    BreachCheckFailed(String),
+}
+
+impl fmt::Display for PasswordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PasswordError::TooShort { min, actual } => write!(
+                f,
+                "must be at least {} characters long, got {}",
+                min, actual
+            ),
+            PasswordError::TooLong { max, actual } => write!(
+                f,
+                "must be at most {} characters long, got {}",
+                max, actual
+            ),
+            PasswordError::MissingClass { class, min: 1, .. } => {
+                write!(f, "must contain {}", class)
+            }
+            PasswordError::MissingClass { class, min, actual } => write!(
+                f,
+                "must contain at least {} {}, got {}",
+                min,
+                class.plural(),
+                actual
+            ),
+            PasswordError::NotEnoughClasses { min, actual } => write!(
+                f,
+                "must contain at least {} of uppercase letters, lowercase letters, digits and special characters, got {}",
+                min, actual
+            ),
+            PasswordError::TooWeak { min, actual } => write!(
+                f,
+                "is too easy to guess, strength score {} of 4 is below {}",
+                actual, min
+            ),
+            PasswordError::ForbiddenCharacter {
+                position,
+                character,
+            } => write!(
+                f,
+                "forbidden character {:?} at position {}",
+                character, position
+            ),
+            PasswordError::CustomRule(name) => write!(f, "must satisfy rule '{}'", name),
+            PasswordError::ContainsContext { field } => write!(f, "must not contain {}", field),
+            PasswordError::TooSimilar { min, actual } => write!(
+                f,
+                "must differ from the previous password by at least {} characters, got {}",
+                min, actual
+            ),
+            PasswordError::Reused { last } => {
+                write!(f, "must not match any of the last {} passwords", last)
+            }
+            PasswordError::Breached { count } => write!(
+                f,
+                "must not appear in a known data breach, found {} times",
+                count
+            ),
+            PasswordError::BreachCheckFailed(reason) => {
+                write!(f, "breach check failed: {}", reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PasswordError {}
+
+/// Reasons a phone number can be rejected.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PhoneError {
+    /// The number contains a character that is not a digit.
+    InvalidCharacter { position: usize, character: char },
+    /// The number has fewer digits than allowed.
+    TooShort { min: usize, actual: usize },
+    /// The number has more digits than allowed.
+    TooLong { max: usize, actual: usize },
+    /// The number does not start with `+` followed by a country code.
+    MissingCountryCode,
+    /// No supported country uses the number's calling code.
+    UnknownCountryCode,
+    /// The number is not in the numbering plan of the country.
+    InvalidNumber { country_code: CountryCode },
+    /// The number is of a type the policy does not accept.
+    DisallowedNumberType { number_type: NumberType },
+}
+
+impl fmt::Display for PhoneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PhoneError::InvalidCharacter {
+                position,
+                character,
+            } => write!(
+                f,
+                "invalid character '{}' at position {}",
+                character, position
+            ),
+            PhoneError::TooShort { min, actual } => {
+                write!(f, "must have at least {} digits, got {}", min, actual)
+            }
+            PhoneError::TooLong { max, actual } => {
+                write!(f, "must have at most {} digits, got {}", max, actual)
+            }
+            PhoneError::MissingCountryCode => write!(f, "missing country code"),
+            PhoneError::UnknownCountryCode => write!(f, "unknown country code"),
+            PhoneError::InvalidNumber { country_code } => {
+                write!(f, "not a valid number for country code {}", country_code)
+            }
+            PhoneError::DisallowedNumberType { number_type } => {
+                write!(f, "{} numbers are not accepted", number_type)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PhoneError {}
+
+/// Reasons a URL can be rejected.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UrlError {
+    /// The URL does not start with `<scheme>://`.
+    MissingScheme,
+    /// The scheme is not one of `http`, `https` or `ftp`.
+    UnsupportedScheme(String),
+    /// The host is missing or has no dot-separated label.
+    InvalidHost,
+    /// The URL contains whitespace.
+    InvalidCharacter { position: usize },
+}
+
+impl fmt::Display for UrlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UrlError::MissingScheme => write!(f, "missing scheme"),
+            UrlError::UnsupportedScheme(scheme) => write!(f, "unsupported scheme '{}'", scheme),
+            UrlError::InvalidHost => write!(f, "invalid host"),
+            UrlError::InvalidCharacter { position } => {
+                write!(f, "invalid character at position {}", position)
+            }
+        }
+    }
+}
+
+impl std::error::Error for UrlError {}
+
+/// Reasons a password could not be hashed or a stored hash could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HashError {
+    /// The algorithm's cargo feature is not enabled.
+    UnsupportedAlgorithm(String),
+    /// The stored hash is not a valid PHC or bcrypt string.
+    InvalidFormat,
+    /// The algorithm rejected the given parameters.
+    InvalidParams(String),
+    /// The password is longer than the algorithm can hash, e.g. the 72
+    /// bytes of bcrypt.
+    PasswordTooLong { max: usize, actual: usize },
+    /// The algorithm failed while hashing.
+    Failed(String),
+}
+
+impl fmt::Display for HashError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HashError::UnsupportedAlgorithm(algorithm) => {
+                write!(f, "algorithm '{}' is not enabled", algorithm)
+            }
+            HashError::InvalidFormat => write!(f, "invalid hash format"),
+            HashError::InvalidParams(reason) => write!(f, "invalid parameters: {}", reason),
+            HashError::PasswordTooLong { max, actual } => write!(
+                f,
+                "password is {} bytes long, the algorithm hashes at most {}",
+                actual, max
+            ),
+            HashError::Failed(reason) => write!(f, "hashing failed: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for HashError {}
+
 #[cfg(test)]
 mod tests {
-    use crate::error::TypeError;
+    use crate::error::{EmailError, HashError, PasswordError, TypeError};
+    use crate::password::CharClass;
 
     #[test]
-    fn test_parse_error_display() {
-        let error = TypeError::ParseError("Invalid input".to_string());
-        assert_eq!(format!("{}", error), "Invalid input");
+    fn test_type_error_display() {
+        let error = TypeError::Email(EmailError::MissingAt);
+        assert_eq!(format!("{}", error), "unable to parse email, missing '@'.");
+
+        let error = TypeError::Password(PasswordError::TooShort { min: 8, actual: 5 });
+        assert_eq!(
+            format!("{}", error),
+            "unable to parse password, must be at least 8 characters long, got 5."
+        );
+
+        let error = TypeError::Password(PasswordError::MissingClass {
+            class: CharClass::Digit,
+            min: 1,
+            actual: 0,
+        });
+        assert_eq!(
+            format!("{}", error),
+            "unable to parse password, must contain a digit."
+        );
+
+        let error = TypeError::Password(PasswordError::MissingClass {
+            class: CharClass::Digit,
+            min: 2,
+            actual: 1,
+        });
+        assert_eq!(
+            format!("{}", error),
+            "unable to parse password, must contain at least 2 digits, got 1."
+        );
+
+        let error = TypeError::Hash(HashError::UnsupportedAlgorithm("bcrypt".to_string()));
+        assert_eq!(
+            format!("{}", error),
+            "unable to process password hash, algorithm 'bcrypt' is not enabled."
+        );
+    }
+
+    #[test]
+    fn test_type_error_source() {
+        use std::error::Error;
+
+        let error = TypeError::Email(EmailError::EmptyDomain);
+        assert_eq!(error.source().unwrap().to_string(), "empty domain");
     }
 }
@@ -8,11 +8,11 @@
 //! let email = Email::parse("example@example.com").unwrap();
 //! println!("{}", email);
 //!
-//! let phone_number = PhoneNumber::parse(CountryCode::USA, "1234567890").unwrap();
+//! let phone_number = PhoneNumber::parse(CountryCode::USA, "2015550123").unwrap();
 //! println!("{}", phone_number);
 //!
 //! let password = RawPassword::parse_strict("Valid123!").unwrap();
-//! println!("{}", password);
+//! println!("{}", password); // prints "********"
 //! ```
 //!
 //! # Features
@@ -22,16 +22,31 @@
 //! - Parse and validate passwords with different strength levels.
 
 mod country_code;
+mod distance;
 mod email;
 pub mod error;
 mod password;
 mod phone;
+#[cfg(test)]
+mod test_util;
 mod url;
 
 pub use country_code::CountryCode;
-pub use email::Email;
-pub use password::RawPassword;
-pub use phone::PhoneNumber;
+#[cfg(feature = "hickory")]
+pub use email::HickoryResolver;
+pub use email::{
+    Address, AddressList, Canonicalization, Canonicalize, DisposableDomains, DomainSuggester,
+    Email, EmailMode, EmailPolicy, FreeProviders, Group, Mailbox, RoleAccounts,
+};
+#[cfg(feature = "dns")]
+pub use email::{Deliverability, DnsFuture, DnsResolver, MxRecord};
+pub use password::{
+    AsyncBreachChecker, BreachChecker, BreachFuture, CharClass, ContextField, Feedback,
+    HashedPassword, Hasher, HistoryEntry, PassphraseGenerator, PasswordContext, PasswordGenerator,
+    PasswordHistory, PasswordPolicy, PasswordReport, PasswordRule, RangeFileChecker, RawPassword,
+    RuleCheck, SortedBinaryChecker, Strength,
+};
+pub use phone::{AsYouTypeFormatter, NumberType, PhoneFormat, PhoneNumber, PhonePolicy};
 pub use url::Url;
 
 #[cfg(test)]
@@ -43,6 +58,7 @@ mod integration_test {
     struct User {
         username: String,
         email: Email,
+        #[serde(skip_serializing)]
         password: RawPassword,
         socmed_url: Url,
     }
@@ -85,4 +101,67 @@ mod integration_test {
         let json = serde_json::to_string_pretty(&user).unwrap();
         println!("{}", json);
     }
+
+    #[derive(Debug, Deserialize)]
+    struct StrictUser {
+        #[allow(dead_code)]
+        #[serde(deserialize_with = "RawPassword::deserialize_strict")]
+        password: RawPassword,
+    }
+
+    #[test]
+    fn test_user_round_trip() {
+        let req = RegisterUserRequest {
+            email: "example@example.com".to_string(),
+            password: "Valid123!".to_string(),
+            socmed_url: "https://example.com/useridex".to_string(),
+            username: "user123".to_string(),
+        };
+
+        let user = User::from(req);
+        let mut json = serde_json::to_value(&user).unwrap();
+        assert!(!json.to_string().contains("Valid123!"));
+        json["password"] = "Valid123!".into();
+        let decoded: User = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded.username, user.username);
+        assert_eq!(decoded.email, user.email);
+        assert_eq!(decoded.password, user.password);
+        assert_eq!(decoded.socmed_url, user.socmed_url);
+    }
+
+    #[test]
+    fn test_user_rejects_invalid_json() {
+        let invalid_email = r#"{"username":"user123","email":"not an email","password":"Valid123!","socmed_url":"https://example.com"}"#;
+        assert!(serde_json::from_str::<User>(invalid_email).is_err());
+
+        let invalid_password = r#"{"username":"user123","email":"example@example.com","password":"short","socmed_url":"https://example.com"}"#;
+        assert!(serde_json::from_str::<User>(invalid_password).is_err());
+
+        let invalid_url = r#"{"username":"user123","email":"example@example.com","password":"Valid123!","socmed_url":"example.com"}"#;
+        assert!(serde_json::from_str::<User>(invalid_url).is_err());
+
+        let err = serde_json::from_str::<User>(invalid_email).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("unable to parse email, missing '@'."));
+    }
+
+    #[test]
+    fn test_strict_password_policy_on_deserialize() {
+        assert!(serde_json::from_str::<StrictUser>(r#"{"password":"Valid123!"}"#).is_ok());
+        assert!(serde_json::from_str::<StrictUser>(r#"{"password":"validpass"}"#).is_err());
+    }
+
+    #[test]
+    fn test_password_is_redacted_in_debug() {
+        let user: User = serde_json::from_str(r#"{"username":"user123","email":"example@example.com","password":"Valid123!","socmed_url":"https://example.com"}"#).unwrap();
+        assert!(!format!("{:?}", user).contains("Valid123!"));
+    }
+
+    #[cfg(feature = "serialize-secret")]
+    #[test]
+    fn test_serialize_secret_feature() {
+        let password = RawPassword::parse_strict("Valid123!").unwrap();
+        assert_eq!(serde_json::to_string(&password).unwrap(), "\"Valid123!\"");
+    }
 }
@@ -1,7 +1,27 @@
 use crate::error::TypeError;
-use derive_more::Display;
-use regex::Regex;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt;
+use subtle::ConstantTimeEq;
+use unicode_normalization::UnicodeNormalization;
+use zeroize::{Zeroize, Zeroizing};
+
+mod breach;
+mod context;
+mod generate;
+mod hash;
+mod history;
+mod policy;
+mod strength;
+
+pub use breach::{
+    AsyncBreachChecker, BreachChecker, BreachFuture, RangeFileChecker, SortedBinaryChecker,
+};
+pub use context::{ContextField, PasswordContext};
+pub use generate::{PassphraseGenerator, PasswordGenerator};
+pub use hash::{HashedPassword, Hasher};
+pub use history::{HistoryEntry, PasswordHistory};
+pub use policy::{PasswordPolicy, PasswordReport, PasswordRule, RuleCheck};
+pub use strength::{Feedback, Strength};
 
 /// This crate provides functionality to parse and validate raw passwords with different levels of strength.
 ///
@@ -11,31 +31,299 @@ use serde::{Deserialize, Serialize};
 /// use custom_type::RawPassword;
 ///
 /// let weak_password = RawPassword::parse_weak("weakpass").unwrap();
-/// println!("Weak Password: {}", weak_password);
+/// println!("Weak Password: {}", weak_password); // prints "********"
 ///
 /// let medium_password = RawPassword::parse_medium("Medium123").unwrap();
 /// println!("Medium Password: {}", medium_password);
 ///
 /// let strict_password = RawPassword::parse_strict("Strong1!23").unwrap();
-/// println!("Strong Password: {}", strict_password);
+/// assert_eq!(strict_password.expose_secret(), "Strong1!23");
 /// ```
 ///
 /// - `parse_weak`: Parses a password and ensures it is at least 8 characters long.
 /// - `parse_medium`: Parses a password and ensures it is at least 8 characters long, contains both letters and digits.
 /// - `parse_strict`: Parses a password and ensures it is at least 8 characters long, contains uppercase, lowercase, digits, and special characters.
+/// - `parse_with`: Parses a password against a custom [`PasswordPolicy`]; the three tiers above are its presets.
 ///
 /// Each method returns a `Result<RawPassword, TypeError>` where `TypeError` indicates a parsing error if the password does not meet the criteria.
 ///
+/// Deserializing a `RawPassword` validates it with `parse_weak`. Use one of the
+/// `deserialize_*` functions with `#[serde(deserialize_with = "...")]` to require
+/// a stronger policy:
+///
+/// ```
+/// use custom_type::RawPassword;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Register {
+///     #[serde(deserialize_with = "RawPassword::deserialize_strict")]
+///     password: RawPassword,
+/// }
+///
+/// assert!(serde_json::from_str::<Register>(r#"{"password":"Strong1!23"}"#).is_ok());
+/// assert!(serde_json::from_str::<Register>(r#"{"password":"weakpass"}"#).is_err());
+/// ```
+///
+/// # Secrecy
+///
+/// `RawPassword` renders as `********` through `Display` and `Debug`, and its
+/// memory is zeroed when it is dropped. Read the plaintext with
+/// [`RawPassword::expose_secret`]. Equality is checked in constant time.
+/// `Serialize` writes the plaintext and is therefore only implemented with the
+/// `serialize-secret` cargo feature.
+///
+/// # Unicode
+///
+/// Passwords are NFKC normalized when parsed, so the same password typed on
+/// different keyboards or input methods compares and hashes equally. Lengths
+/// count code points, character classes follow Unicode properties, and
+/// control characters are rejected.
+///
 /// # Features
 ///
 /// - Parse and validate passwords with different strength levels (weak, medium, strict).
+/// - Parse and validate passwords against configurable policies.
 /// - Custom error type `TypeError` for handling invalid passwords.
 /// ### RawPassword : Parse `impl ToString` Into a Valid Password
 /// Provides methods to parse and validate passwords with different strength criteria.
-#[derive(Debug, PartialEq, Display, Serialize, Deserialize)]
 pub struct RawPassword(String);
 
+/// Placeholder shown instead of the password.
+const REDACTED: &str = "********";
+
+impl fmt::Display for RawPassword {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl fmt::Debug for RawPassword {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl PartialEq for RawPassword {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_bytes().ct_eq(other.0.as_bytes()).into()
+    }
+}
+
+impl Eq for RawPassword {}
+
+impl Drop for RawPassword {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+#[cfg(feature = "serialize-secret")]
+impl Serialize for RawPassword {
+    /// Serializes the plaintext password.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for RawPassword {
+    /// Deserializes a string and validates it with [`RawPassword::parse_weak`].
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Self::deserialize_weak(deserializer)
+    }
+}
+
 impl RawPassword {
+    /// Parses a given string into a password that satisfies `policy`.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - The rules the password must satisfy.
+    /// * `password` - A string slice that holds the password to be parsed.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` if the password satisfies every rule of the policy.
+    /// * `Err(TypeError::Password)` with the first violated rule otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use custom_type::{PasswordPolicy, RawPassword};
+    ///
+    /// let policy = PasswordPolicy::new().min_length(12).min_classes(3);
+    ///
+    /// let password = RawPassword::parse_with(&policy, "Tenant-Secret1");
+    /// assert!(password.is_ok());
+    ///
+    /// let invalid_password = RawPassword::parse_with(&policy, "tenantsecret");
+    /// assert!(invalid_password.is_err());
+    /// ```
+    pub fn parse_with(policy: &PasswordPolicy, password: impl ToString) -> Result<Self, TypeError> {
+        Self::parse_with_context(policy, &PasswordContext::new(), password)
+    }
+
+    /// Parses a given string into a password that satisfies `policy` for the
+    /// account described by `context`.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - The rules the password must satisfy.
+    /// * `context` - The account details the context rules check against.
+    /// * `password` - A string slice that holds the password to be parsed.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` if the password satisfies every rule of the policy.
+    /// * `Err(TypeError::Password)` with the first violated rule otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use custom_type::{PasswordContext, PasswordPolicy, RawPassword};
+    ///
+    /// let policy = PasswordPolicy::weak().min_edit_distance(3);
+    /// let previous = RawPassword::parse_weak("Summer2023!").unwrap();
+    /// let context = PasswordContext::new().previous_password(&previous);
+    ///
+    /// let password = RawPassword::parse_with_context(&policy, &context, "Autumn-leaves-7");
+    /// assert!(password.is_ok());
+    ///
+    /// let invalid_password = RawPassword::parse_with_context(&policy, &context, "Summer2024!");
+    /// assert!(invalid_password.is_err());
+    /// ```
+    pub fn parse_with_context(
+        policy: &PasswordPolicy,
+        context: &PasswordContext,
+        password: impl ToString,
+    ) -> Result<Self, TypeError> {
+        let password = Self::normalized(password);
+        policy.check(&password.0, context)?;
+        Ok(password)
+    }
+
+    /// Returns the plaintext password.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use custom_type::RawPassword;
+    ///
+    /// let password = RawPassword::parse_weak("validpass").unwrap();
+    /// assert_eq!(password.to_string(), "********");
+    /// assert_eq!(password.expose_secret(), "validpass");
+    /// ```
+    pub fn expose_secret(&self) -> &str {
+        &self.0
+    }
+
+    /// Checks a password against every rule of `policy` without stopping at
+    /// the first violation.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - The rules the password must satisfy.
+    /// * `password` - A string slice that holds the password to be checked.
+    ///
+    /// # Returns
+    ///
+    /// A [`PasswordReport`] listing which rules are satisfied and which are violated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use custom_type::{PasswordPolicy, RawPassword};
+    ///
+    /// let report = RawPassword::validate(&PasswordPolicy::strict(), "Valid123!");
+    /// assert!(report.is_valid());
+    ///
+    /// let report = RawPassword::validate(&PasswordPolicy::strict(), "valid");
+    /// assert_eq!(report.violations().count(), 4);
+    /// ```
+    pub fn validate(policy: &PasswordPolicy, password: &str) -> PasswordReport {
+        Self::validate_with_context(policy, &PasswordContext::new(), password)
+    }
+
+    /// Checks a password against every rule of `policy` for the account
+    /// described by `context`, without stopping at the first violation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use custom_type::{ContextField, PasswordContext, PasswordPolicy, RawPassword};
+    /// use custom_type::error::PasswordError;
+    ///
+    /// let policy = PasswordPolicy::strict().forbid_context();
+    /// let context = PasswordContext::new().username("marvin");
+    /// let report = RawPassword::validate_with_context(&policy, &context, "M4rvin-2024!");
+    /// assert_eq!(
+    ///     report.violations().collect::<Vec<_>>(),
+    ///     vec![&PasswordError::ContainsContext { field: ContextField::Username }]
+    /// );
+    /// ```
+    pub fn validate_with_context(
+        policy: &PasswordPolicy,
+        context: &PasswordContext,
+        password: &str,
+    ) -> PasswordReport {
+        policy.report(&Self::normalized(password).0, context)
+    }
+
+    /// Wraps the NFKC normalization of `password`, zeroing the intermediate
+    /// copy so that the plaintext is zeroed even when validation fails.
+    fn normalized(password: impl ToString) -> Self {
+        let raw = Zeroizing::new(password.to_string());
+        Self(raw.nfkc().collect())
+    }
+
+    /// Parses a given string into a password whose estimated [`Strength`]
+    /// score is at least `score`.
+    ///
+    /// # Arguments
+    ///
+    /// * `score` - The minimum score, from 0 to 4.
+    /// * `password` - A string slice that holds the password to be parsed.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` if the password is hard enough to guess.
+    /// * `Err(TypeError::Password)` if the password is too easy to guess.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use custom_type::RawPassword;
+    ///
+    /// let password = RawPassword::parse_min_strength(3, "correct-horse-battery-staple");
+    /// assert!(password.is_ok());
+    ///
+    /// let invalid_password = RawPassword::parse_min_strength(3, "Password1!");
+    /// assert!(invalid_password.is_err());
+    /// ```
+    pub fn parse_min_strength(score: u8, password: impl ToString) -> Result<Self, TypeError> {
+        Self::parse_with(&PasswordPolicy::new().min_strength(score), password)
+    }
+
+    /// Estimates how hard the password is to guess.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use custom_type::RawPassword;
+    ///
+    /// let password = RawPassword::parse_strict("Password1!").unwrap();
+    /// assert!(password.strength().score() <= 1);
+    /// ```
+    pub fn strength(&self) -> Strength {
+        Strength::estimate(&self.0)
+    }
+
     /// Parses a given string into a weak password.
     ///
     /// A weak password must be at least 8 characters long.
@@ -47,7 +335,7 @@ impl RawPassword {
     /// # Returns
     ///
     /// * `Ok(Self)` if the password meets the weak criteria.
-    /// * `Err(TypeError::ParseError)` if the password is invalid.
+    /// * `Err(TypeError::Password)` if the password is invalid.
     ///
     /// # Examples
     ///
@@ -61,14 +349,7 @@ impl RawPassword {
     /// assert!(invalid_password.is_err());
     /// ```
     pub fn parse_weak(password: impl ToString) -> Result<Self, TypeError> {
-        let password_str = password.to_string();
-        if password_str.len() >= 8 {
-            Ok(Self(password_str))
-        } else {
-            Err(TypeError::ParseError(String::from(
-                "Weak password: must be at least 8 characters long",
-            )))
-        }
+        Self::parse_with(&PasswordPolicy::weak(), password)
     }
 
     /// Parses a given string into a medium password.
@@ -82,7 +363,7 @@ impl RawPassword {
     /// # Returns
     ///
     /// * `Ok(Self)` if the password meets the medium criteria.
-    /// * `Err(TypeError::ParseError)` if the password is invalid.
+    /// * `Err(TypeError::Password)` if the password is invalid.
     ///
     /// # Examples
     ///
@@ -96,17 +377,7 @@ impl RawPassword {
     /// assert!(invalid_password.is_err());
     /// ```
     pub fn parse_medium(password: impl ToString) -> Result<Self, TypeError> {
-        let password_str = password.to_string();
-        let re_digit = Regex::new(r"\d").unwrap();
-        let re_alpha = Regex::new(r"[a-zA-Z]").unwrap();
-        if password_str.len() >= 8
-            && re_digit.is_match(&password_str)
-            && re_alpha.is_match(&password_str)
-        {
-            Ok(Self(password_str))
-        } else {
-            Err(TypeError::ParseError(String::from("Medium password: must be at least 8 characters long and contain both letters and digits")))
-        }
+        Self::parse_with(&PasswordPolicy::medium(), password)
     }
 
     /// Parses a given string into a strict password.
@@ -120,7 +391,7 @@ impl RawPassword {
     /// # Returns
     ///
     /// * `Ok(Self)` if the password meets the strict criteria.
-    /// * `Err(TypeError::ParseError)` if the password is invalid.
+    /// * `Err(TypeError::Password)` if the password is invalid.
     ///
     /// # Examples
     ///
@@ -134,20 +405,87 @@ impl RawPassword {
     /// assert!(invalid_password.is_err());
     /// ```
     pub fn parse_strict(password: impl ToString) -> Result<Self, TypeError> {
-        let password_str = password.to_string();
-        let re_upper = Regex::new(r"[A-Z]").unwrap();
-        let re_lower = Regex::new(r"[a-z]").unwrap();
-        let re_digit = Regex::new(r"\d").unwrap();
-        let re_special = Regex::new(r"[^a-zA-Z\d]").unwrap();
-        if password_str.len() >= 8
-            && re_upper.is_match(&password_str)
-            && re_lower.is_match(&password_str)
-            && re_digit.is_match(&password_str)
-            && re_special.is_match(&password_str)
-        {
-            Ok(Self(password_str))
-        } else {
-            Err(TypeError::ParseError(String::from("Strict password: must be at least 8 characters long and contain uppercase, lowercase, digits, and special characters")))
+        Self::parse_with(&PasswordPolicy::strict(), password)
+    }
+
+    /// Deserializes a string and validates it with [`RawPassword::parse_weak`].
+    pub fn deserialize_weak<'de, D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let password = Zeroizing::new(String::deserialize(deserializer)?);
+        Self::parse_weak(password.as_str()).map_err(serde::de::Error::custom)
+    }
+
+    /// Deserializes a string and validates it with [`RawPassword::parse_medium`].
+    pub fn deserialize_medium<'de, D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let password = Zeroizing::new(String::deserialize(deserializer)?);
+        Self::parse_medium(password.as_str()).map_err(serde::de::Error::custom)
+    }
+
+    /// Deserializes a string and validates it with [`RawPassword::parse_strict`].
+    pub fn deserialize_strict<'de, D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let password = Zeroizing::new(String::deserialize(deserializer)?);
+        Self::parse_strict(password.as_str()).map_err(serde::de::Error::custom)
+    }
+}
+
+/// A class of characters a password can be required to contain. Classes
+/// follow Unicode properties, so `É` is uppercase and `ß` is lowercase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CharClass {
+    /// Any uppercase letter, such as `A` or `Ä`.
+    Uppercase,
+    /// Any lowercase letter, such as `a` or `ß`.
+    Lowercase,
+    /// Any letter, including those without case such as `語`.
+    Letter,
+    /// Any numeric character, such as `7` or `٣`.
+    Digit,
+    /// Anything that is not a letter or a digit.
+    Special,
+}
+
+impl CharClass {
+    /// Returns `true` if `c` belongs to this class.
+    pub fn matches(&self, c: char) -> bool {
+        match self {
+            CharClass::Uppercase => c.is_uppercase(),
+            CharClass::Lowercase => c.is_lowercase(),
+            CharClass::Letter => c.is_alphabetic(),
+            CharClass::Digit => c.is_numeric(),
+            CharClass::Special => !c.is_alphanumeric(),
+        }
+    }
+}
+
+impl fmt::Display for CharClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CharClass::Uppercase => write!(f, "an uppercase letter"),
+            CharClass::Lowercase => write!(f, "a lowercase letter"),
+            CharClass::Letter => write!(f, "a letter"),
+            CharClass::Digit => write!(f, "a digit"),
+            CharClass::Special => write!(f, "a special character"),
+        }
+    }
+}
+
+impl CharClass {
+    /// Returns the plural name of the class, e.g. `"digits"`.
+    pub(crate) fn plural(&self) -> &'static str {
+        match self {
+            CharClass::Uppercase => "uppercase letters",
+            CharClass::Lowercase => "lowercase letters",
+            CharClass::Letter => "letters",
+            CharClass::Digit => "digits",
+            CharClass::Special => "special characters",
         }
     }
 }
@@ -158,14 +496,16 @@ impl RawPassword {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::PasswordError;
 
     #[test]
     fn test_parse_weak() {
         assert_eq!(
             RawPassword::parse_weak("short".to_string()),
-            Err(TypeError::ParseError(String::from(
-                "Weak password: must be at least 8 characters long"
-            )))
+            Err(TypeError::Password(PasswordError::TooShort {
+                min: 8,
+                actual: 5
+            }))
         );
         assert_eq!(
             RawPassword::parse_weak("validpass".to_string()),
@@ -177,11 +517,18 @@ mod tests {
     fn test_parse_medium() {
         assert_eq!(
             RawPassword::parse_medium("short".to_string()),
-            Err(TypeError::ParseError(String::from("Medium password: must be at least 8 characters long and contain both letters and digits")))
+            Err(TypeError::Password(PasswordError::TooShort {
+                min: 8,
+                actual: 5
+            }))
         );
         assert_eq!(
             RawPassword::parse_medium("noDigits".to_string()),
-            Err(TypeError::ParseError(String::from("Medium password: must be at least 8 characters long and contain both letters and digits")))
+            Err(TypeError::Password(PasswordError::MissingClass {
+                class: CharClass::Digit,
+                min: 1,
+                actual: 0
+            }))
         );
         assert_eq!(
             RawPassword::parse_medium("valid123".to_string()),
@@ -193,23 +540,107 @@ mod tests {
     fn test_parse_strict() {
         assert_eq!(
             RawPassword::parse_strict("short".to_string()),
-            Err(TypeError::ParseError(String::from("Strict password: must be at least 8 characters long and contain uppercase, lowercase, digits, and special characters")))
+            Err(TypeError::Password(PasswordError::TooShort {
+                min: 8,
+                actual: 5
+            }))
         );
         assert_eq!(
             RawPassword::parse_strict("NoDigits!".to_string()),
-            Err(TypeError::ParseError(String::from("Strict password: must be at least 8 characters long and contain uppercase, lowercase, digits, and special characters")))
+            Err(TypeError::Password(PasswordError::MissingClass {
+                class: CharClass::Digit,
+                min: 1,
+                actual: 0
+            }))
         );
         assert_eq!(
             RawPassword::parse_strict("noupper1!".to_string()),
-            Err(TypeError::ParseError(String::from("Strict password: must be at least 8 characters long and contain uppercase, lowercase, digits, and special characters")))
+            Err(TypeError::Password(PasswordError::MissingClass {
+                class: CharClass::Uppercase,
+                min: 1,
+                actual: 0
+            }))
         );
         assert_eq!(
             RawPassword::parse_strict("VALID123".to_string()),
-            Err(TypeError::ParseError(String::from("Strict password: must be at least 8 characters long and contain uppercase, lowercase, digits, and special characters")))
+            Err(TypeError::Password(PasswordError::MissingClass {
+                class: CharClass::Lowercase,
+                min: 1,
+                actual: 0
+            }))
         );
         assert_eq!(
             RawPassword::parse_strict("Valid123!".to_string()),
             Ok(RawPassword("Valid123!".to_string()))
         );
     }
+
+    #[test]
+    fn test_redacted_output() {
+        let password = RawPassword::parse_weak("validpass").unwrap();
+        assert_eq!(format!("{}", password), "********");
+        assert_eq!(format!("{:?}", password), "********");
+        assert_eq!(password.expose_secret(), "validpass");
+    }
+
+    #[test]
+    fn test_constant_time_equality() {
+        let password = RawPassword::parse_weak("validpass").unwrap();
+        assert_eq!(password, RawPassword::parse_weak("validpass").unwrap());
+        assert_ne!(password, RawPassword::parse_weak("validpast").unwrap());
+        assert_ne!(password, RawPassword::parse_weak("validpass1").unwrap());
+    }
+
+    #[test]
+    fn test_nfkc_normalization() {
+        let password = RawPassword::parse_weak("ｐａｓｓｗｏｒｄ１").unwrap();
+        assert_eq!(password.expose_secret(), "password1");
+        // Composed and decomposed spellings are the same password.
+        assert_eq!(
+            RawPassword::parse_weak("Fu\u{0308}nfundzwanzig").unwrap(),
+            RawPassword::parse_weak("F\u{00FC}nfundzwanzig").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_unicode_classes() {
+        assert!(RawPassword::parse_strict("Äpfelmuß7!").is_ok());
+        assert!(RawPassword::parse_medium("пароль١٢٣").is_ok());
+        assert_eq!(
+            RawPassword::parse_strict("ÄPFELMUS7!"),
+            Err(TypeError::Password(PasswordError::MissingClass {
+                class: CharClass::Lowercase,
+                min: 1,
+                actual: 0
+            }))
+        );
+    }
+
+    #[test]
+    fn test_rejects_control_characters() {
+        assert_eq!(
+            RawPassword::parse_weak("valid\u{7}pass"),
+            Err(TypeError::Password(PasswordError::ForbiddenCharacter {
+                position: 5,
+                character: '\u{7}'
+            }))
+        );
+        assert!(RawPassword::parse_with(&PasswordPolicy::new(), "\n").is_err());
+    }
+
+    #[test]
+    fn test_grapheme_length() {
+        let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+        let password = family.repeat(3);
+        assert!(RawPassword::parse_weak(&password).is_ok());
+
+        let policy = PasswordPolicy::weak().count_graphemes();
+        assert_eq!(
+            RawPassword::parse_with(&policy, &password),
+            Err(TypeError::Password(PasswordError::TooShort {
+                min: 8,
+                actual: 3
+            }))
+        );
+    }
 }
@@ -0,0 +1,280 @@
+use crate::error::{PasswordError, TypeError};
+use crate::password::{PasswordPolicy, RawPassword};
+use sha1::{Digest, Sha1};
+use std::fs::File;
+use std::future::Future;
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::PathBuf;
+use std::pin::Pin;
+
+/// Length in bytes of a SHA-1 digest.
+const DIGEST_LEN: usize = 20;
+
+/// Length in hex characters of the prefix used to name range files.
+const PREFIX_LEN: usize = 5;
+
+/// ### BreachChecker : Known-Breach Lookup
+/// Looks up a password in a corpus of breached passwords, such as
+/// [Pwned Passwords](https://haveibeenpwned.com/Passwords). Checkers only ever
+/// see the SHA-1 digest of the password, never the plaintext.
+///
+/// Attach a checker to a policy with [`PasswordPolicy::breach_checker`].
+///
+/// # Example
+///
+/// ```no_run
+/// use custom_type::{PasswordPolicy, RangeFileChecker, RawPassword};
+///
+/// let policy = PasswordPolicy::strict().breach_checker(RangeFileChecker::new("pwned/"));
+/// assert!(RawPassword::parse_with(&policy, "P@ssw0rd").is_err());
+/// ```
+pub trait BreachChecker: Send + Sync {
+    /// Returns how many times the password with the given SHA-1 digest
+    /// appears in the corpus, or `0` if it does not.
+    fn occurrences(&self, sha1: &[u8; 20]) -> io::Result<u64>;
+}
+
+/// The future returned by [`AsyncBreachChecker::occurrences_async`].
+pub type BreachFuture<'a> = Pin<Box<dyn Future<Output = io::Result<u64>> + Send + 'a>>;
+
+/// ### AsyncBreachChecker : Known-Breach Lookup Without Blocking
+/// The async counterpart of [`BreachChecker`], for corpora behind a network
+/// service or an async file system. Every [`BreachChecker`] is also an
+/// `AsyncBreachChecker` that completes immediately.
+///
+/// Use it with [`RawPassword::parse_with_async`].
+pub trait AsyncBreachChecker: Send + Sync {
+    /// Returns how many times the password with the given SHA-1 digest
+    /// appears in the corpus, or `0` if it does not.
+    fn occurrences_async<'a>(&'a self, sha1: &'a [u8; 20]) -> BreachFuture<'a>;
+}
+
+impl<T: BreachChecker + ?Sized> AsyncBreachChecker for T {
+    fn occurrences_async<'a>(&'a self, sha1: &'a [u8; 20]) -> BreachFuture<'a> {
+        Box::pin(std::future::ready(self.occurrences(sha1)))
+    }
+}
+
+/// ### RangeFileChecker : Pwned Passwords Range Files
+/// Reads a directory of range files in the Pwned Passwords downloadable
+/// format: one `<PREFIX>.txt` file per 5-hex-digit SHA-1 prefix, each line
+/// holding the remaining 35 hex digits and a count, as in
+/// `1E4C9B93F3F0682250B6CF8331B7EE68FD8:3861493`.
+///
+/// Only the file for the password's prefix is read. A missing file is
+/// reported as an error rather than as "not breached".
+#[derive(Debug, Clone)]
+pub struct RangeFileChecker {
+    dir: PathBuf,
+}
+
+impl RangeFileChecker {
+    /// Creates a checker reading range files from `dir`.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+}
+
+impl BreachChecker for RangeFileChecker {
+    fn occurrences(&self, sha1: &[u8; 20]) -> io::Result<u64> {
+        let hex = to_hex(sha1);
+        let (prefix, suffix) = hex.split_at(PREFIX_LEN);
+        let file = File::open(self.dir.join(format!("{}.txt", prefix)))?;
+
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            let Some((hash, count)) = line.trim_end().split_once(':') else {
+                continue;
+            };
+            if hash.eq_ignore_ascii_case(suffix) {
+                return count
+                    .parse()
+                    .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "invalid count"));
+            }
+        }
+        Ok(0)
+    }
+}
+
+/// ### SortedBinaryChecker : Sorted SHA-1 Digest File
+/// Reads a file of raw 20-byte SHA-1 digests sorted in ascending order, and
+/// finds a digest with a binary search over the file. The format carries no
+/// counts, so a breached password is reported as occurring once.
+#[derive(Debug, Clone)]
+pub struct SortedBinaryChecker {
+    path: PathBuf,
+}
+
+impl SortedBinaryChecker {
+    /// Creates a checker reading digests from `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl BreachChecker for SortedBinaryChecker {
+    fn occurrences(&self, sha1: &[u8; 20]) -> io::Result<u64> {
+        let mut file = File::open(&self.path)?;
+        let len = file.metadata()?.len();
+        if len % DIGEST_LEN as u64 != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "file length is not a multiple of 20 bytes",
+            ));
+        }
+
+        let (mut low, mut high) = (0, len / DIGEST_LEN as u64);
+        let mut record = [0u8; DIGEST_LEN];
+        while low < high {
+            let mid = low + (high - low) / 2;
+            file.seek(SeekFrom::Start(mid * DIGEST_LEN as u64))?;
+            file.read_exact(&mut record)?;
+            match record.cmp(sha1) {
+                std::cmp::Ordering::Less => low = mid + 1,
+                std::cmp::Ordering::Greater => high = mid,
+                std::cmp::Ordering::Equal => return Ok(1),
+            }
+        }
+        Ok(0)
+    }
+}
+
+/// Returns the SHA-1 digest of `password`.
+pub(crate) fn sha1(password: &str) -> [u8; 20] {
+    Sha1::digest(password.as_bytes()).into()
+}
+
+/// Converts a breach lookup into the policy violation it implies, if any.
+pub(crate) fn violation(occurrences: io::Result<u64>) -> Option<PasswordError> {
+    match occurrences {
+        Ok(0) => None,
+        Ok(count) => Some(PasswordError::Breached { count }),
+        Err(err) => Some(PasswordError::BreachCheckFailed(err.to_string())),
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02X}", b)).collect()
+}
+
+impl RawPassword {
+    /// Parses a password against `policy`, then looks it up with an async
+    /// breach `checker`.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - The rules the password must satisfy.
+    /// * `checker` - The breach corpus to look the password up in.
+    /// * `password` - The password to be parsed.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` if the password satisfies the policy and is not breached.
+    /// * `Err(TypeError::Password)` with the first violation otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use custom_type::{PasswordPolicy, RangeFileChecker, RawPassword};
+    ///
+    /// # async fn register() {
+    /// let checker = RangeFileChecker::new("pwned/");
+    /// let password =
+    ///     RawPassword::parse_with_async(&PasswordPolicy::strict(), &checker, "Valid123!").await;
+    /// # }
+    /// ```
+    pub async fn parse_with_async(
+        policy: &PasswordPolicy,
+        checker: &dyn AsyncBreachChecker,
+        password: impl ToString,
+    ) -> Result<Self, TypeError> {
+        let password = Self::parse_with(policy, password)?;
+        let digest = sha1(password.expose_secret());
+        match violation(checker.occurrences_async(&digest).await) {
+            Some(violation) => Err(violation.into()),
+            None => Ok(password),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::block_on;
+    use std::path::Path;
+
+    fn fixture(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures")
+            .join(name)
+    }
+
+    #[test]
+    fn test_sha1() {
+        assert_eq!(
+            to_hex(&sha1("password")),
+            "5BAA61E4C9B93F3F0682250B6CF8331B7EE68FD8"
+        );
+    }
+
+    #[test]
+    fn test_range_file_checker() {
+        let checker = RangeFileChecker::new(fixture("pwned"));
+        assert_eq!(checker.occurrences(&sha1("password")).unwrap(), 3861493);
+        assert_eq!(checker.occurrences(&sha1("Valid123!")).unwrap(), 3);
+        // Same prefix file as "password", but not listed.
+        let mut unlisted = sha1("password");
+        unlisted[19] ^= 1;
+        assert_eq!(checker.occurrences(&unlisted).unwrap(), 0);
+        // No range file for this prefix.
+        assert!(checker.occurrences(&sha1("Correct-horse-42")).is_err());
+    }
+
+    #[test]
+    fn test_sorted_binary_checker() {
+        let checker = SortedBinaryChecker::new(fixture("pwned.bin"));
+        assert_eq!(checker.occurrences(&sha1("password")).unwrap(), 1);
+        assert_eq!(checker.occurrences(&sha1("letmein")).unwrap(), 1);
+        assert_eq!(checker.occurrences(&sha1("Correct-horse-42")).unwrap(), 0);
+        assert_eq!(checker.occurrences(&[0; 20]).unwrap(), 0);
+        assert_eq!(checker.occurrences(&[0xFF; 20]).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_policy_breach_checker() {
+        let policy =
+            PasswordPolicy::strict().breach_checker(SortedBinaryChecker::new(fixture("pwned.bin")));
+        assert_eq!(
+            RawPassword::parse_with(&policy, "Valid123!"),
+            Err(TypeError::Password(PasswordError::Breached { count: 1 }))
+        );
+        assert!(RawPassword::parse_with(&policy, "Correct-horse-42").is_ok());
+
+        let policy = PasswordPolicy::weak().breach_checker(SortedBinaryChecker::new("missing.bin"));
+        assert!(matches!(
+            RawPassword::parse_with(&policy, "Correct-horse-42"),
+            Err(TypeError::Password(PasswordError::BreachCheckFailed(_)))
+        ));
+    }
+
+    #[test]
+    fn test_parse_with_async() {
+        let checker = RangeFileChecker::new(fixture("pwned"));
+        let policy = PasswordPolicy::strict();
+        assert_eq!(
+            block_on(RawPassword::parse_with_async(
+                &policy,
+                &checker,
+                "Valid123!"
+            )),
+            Err(TypeError::Password(PasswordError::Breached { count: 3 }))
+        );
+        assert_eq!(
+            block_on(RawPassword::parse_with_async(&policy, &checker, "short")),
+            Err(TypeError::Password(PasswordError::TooShort {
+                min: 8,
+                actual: 5
+            }))
+        );
+    }
+}
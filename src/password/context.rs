@@ -0,0 +1,194 @@
+use crate::password::strength::l33t_candidates;
+use crate::password::RawPassword;
+use crate::Email;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use unicode_normalization::UnicodeNormalization;
+
+/// Context tokens shorter than this are ignored, so that short names such as
+/// "Al" do not reject every password containing them.
+const MIN_TOKEN_LEN: usize = 3;
+
+/// ### PasswordContext : Facts About the Account
+/// The account details a password is checked against by
+/// [`PasswordPolicy::forbid_context`](crate::PasswordPolicy::forbid_context) and
+/// [`PasswordPolicy::min_edit_distance`](crate::PasswordPolicy::min_edit_distance).
+/// Pass it to [`RawPassword::parse_with_context`].
+///
+/// # Example
+///
+/// ```
+/// use custom_type::{Email, PasswordContext, PasswordPolicy, RawPassword};
+///
+/// let policy = PasswordPolicy::weak().forbid_context();
+/// let context = PasswordContext::new()
+///     .username("jdoe")
+///     .email(&Email::parse("jane.doe@example.com").unwrap())
+///     .name("Jane Doe");
+///
+/// assert!(RawPassword::parse_with_context(&policy, &context, "correct-horse").is_ok());
+/// assert!(RawPassword::parse_with_context(&policy, &context, "Jd0e-2024!").is_err());
+/// ```
+#[derive(Debug, Default)]
+pub struct PasswordContext {
+    tokens: Vec<(ContextField, String)>,
+    previous: Option<RawPassword>,
+}
+
+/// The kind of account detail a [`PasswordContext`] token came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ContextField {
+    Username,
+    Email,
+    Name,
+    Company,
+    Custom,
+}
+
+impl fmt::Display for ContextField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContextField::Username => write!(f, "the username"),
+            ContextField::Email => write!(f, "the email address"),
+            ContextField::Name => write!(f, "the name"),
+            ContextField::Company => write!(f, "the company name"),
+            ContextField::Custom => write!(f, "a forbidden word"),
+        }
+    }
+}
+
+impl PasswordContext {
+    /// Creates an empty context.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the account's username.
+    pub fn username(self, username: impl ToString) -> Self {
+        self.token(ContextField::Username, username)
+    }
+
+    /// Adds the local part of the account's email address, and each of its
+    /// `.`, `_`, `-` or `+` separated parts.
+    pub fn email(self, email: &Email) -> Self {
+        let email = email.to_string();
+        let local = email
+            .rsplit_once('@')
+            .map_or(email.as_str(), |(local, _)| local);
+        self.words(ContextField::Email, local, &['.', '_', '-', '+'])
+    }
+
+    /// Adds the user's display name, and each of its words.
+    pub fn name(self, name: impl ToString) -> Self {
+        self.words(ContextField::Name, &name.to_string(), &[' ', '-'])
+    }
+
+    /// Adds the company name, and each of its words.
+    pub fn company(self, company: impl ToString) -> Self {
+        self.words(ContextField::Company, &company.to_string(), &[' ', '-'])
+    }
+
+    /// Adds a custom token, such as the product or site name.
+    pub fn custom(self, token: impl ToString) -> Self {
+        self.token(ContextField::Custom, token)
+    }
+
+    /// Sets the password being replaced, for
+    /// [`PasswordPolicy::min_edit_distance`](crate::PasswordPolicy::min_edit_distance).
+    pub fn previous_password(mut self, previous: &RawPassword) -> Self {
+        self.previous = Some(RawPassword(previous.expose_secret().to_string()));
+        self
+    }
+
+    /// Returns the password being replaced, if any.
+    pub(crate) fn previous(&self) -> Option<&RawPassword> {
+        self.previous.as_ref()
+    }
+
+    /// Returns the field of the first token `password` contains, ignoring
+    /// case and common l33t substitutions.
+    pub(crate) fn find_token(&self, password: &str) -> Option<ContextField> {
+        let password: Vec<char> = password.chars().flat_map(char::to_lowercase).collect();
+        self.tokens
+            .iter()
+            .find(|(_, token)| contains_token(&password, token))
+            .map(|(field, _)| *field)
+    }
+
+    /// Adds `token` NFKC normalized and lowercased, like the passwords it is
+    /// compared with.
+    fn token(mut self, field: ContextField, token: impl ToString) -> Self {
+        let token = token.to_string().nfkc().collect::<String>().to_lowercase();
+        if token.chars().count() >= MIN_TOKEN_LEN {
+            self.tokens.push((field, token));
+        }
+        self
+    }
+
+    fn words(self, field: ContextField, value: &str, separators: &[char]) -> Self {
+        let value: String = value.nfkc().collect();
+        let mut context = self.token(field, value.trim());
+        let words: Vec<&str> = value.split(separators).collect();
+        if words.len() > 1 {
+            for word in words {
+                context = context.token(field, word);
+            }
+        }
+        context
+    }
+}
+
+/// Returns `true` if the lowercase `token` occurs in `password`, where each
+/// password character may also be a l33t spelling of the token character.
+fn contains_token(password: &[char], token: &str) -> bool {
+    let token: Vec<char> = token.chars().collect();
+    password.windows(token.len()).any(|window| {
+        window
+            .iter()
+            .zip(&token)
+            .all(|(p, t)| p == t || l33t_candidates(*p).is_some_and(|letters| letters.contains(t)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_token() {
+        let context = PasswordContext::new()
+            .username("jdoe")
+            .email(&Email::parse("jane.doe@example.com").unwrap())
+            .company("Acme Corp")
+            .custom("widgets");
+
+        assert_eq!(context.find_token("xxJDOExx"), Some(ContextField::Username));
+        assert_eq!(context.find_token("j4n3!"), Some(ContextField::Email));
+        assert_eq!(
+            context.find_token("@cm3-rocks"),
+            Some(ContextField::Company)
+        );
+        assert_eq!(context.find_token("w1dg3t5"), Some(ContextField::Custom));
+        assert_eq!(context.find_token("correct-horse"), None);
+    }
+
+    #[test]
+    fn test_short_tokens_are_ignored() {
+        let context = PasswordContext::new().name("Al Li");
+        assert_eq!(context.find_token("always-lit"), None);
+        assert_eq!(context.find_token("x-al li-x"), Some(ContextField::Name));
+    }
+
+    #[test]
+    fn test_tokens_are_normalized() {
+        let context = PasswordContext::new()
+            .username("ｊｄｏｅ")
+            .name("Ｊａｎｅ　Ｄｏｅ");
+        let password = RawPassword::parse_weak("xxJDOExx").unwrap();
+        assert_eq!(
+            context.find_token(password.expose_secret()),
+            Some(ContextField::Username)
+        );
+        assert_eq!(context.find_token("x-jane-x"), Some(ContextField::Name));
+    }
+}
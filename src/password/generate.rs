@@ -0,0 +1,386 @@
+use crate::error::TypeError;
+use crate::password::policy::COUNTED_CLASSES;
+use crate::password::{CharClass, PasswordPolicy, RawPassword};
+use rand::rngs::OsRng;
+use rand::seq::SliceRandom;
+use std::sync::OnceLock;
+use zeroize::Zeroizing;
+
+/// Printable ASCII characters the generator draws from.
+const ALPHABET: &str = concat!(
+    "ABCDEFGHIJKLMNOPQRSTUVWXYZ",
+    "abcdefghijklmnopqrstuvwxyz",
+    "0123456789",
+    "!#$%&()*+,-./:;<=>?@[]^_{}~",
+);
+
+/// Characters that are easily confused with one another when read aloud or
+/// copied from a screen.
+const AMBIGUOUS: &str = "0O1lI|";
+
+/// Length of generated passwords when the policy does not require more.
+const DEFAULT_LENGTH: usize = 16;
+
+/// How many candidates to try before giving up on a policy, e.g. one with a
+/// custom rule that rejects almost everything.
+const MAX_ATTEMPTS: usize = 100;
+
+/// A list of 6^4 short, common English words compiled for this crate, one
+/// per line.
+const WORDLIST: &str = include_str!("data/passphrase_words.txt");
+
+/// ### PasswordGenerator : Random Passwords Satisfying a Policy
+/// Generates random passwords from the operating system's CSPRNG. Every
+/// generated password is checked against the policy before it is returned,
+/// so it always parses with that policy.
+///
+/// # Example
+///
+/// ```
+/// use custom_type::{PasswordGenerator, PasswordPolicy, RawPassword};
+///
+/// let generator = PasswordGenerator::new(PasswordPolicy::strict()).exclude_ambiguous();
+/// let password = generator.generate().unwrap();
+///
+/// assert_eq!(password.expose_secret().chars().count(), 16);
+/// assert!(RawPassword::parse_strict(password.expose_secret()).is_ok());
+/// ```
+#[derive(Debug, Clone)]
+pub struct PasswordGenerator {
+    policy: PasswordPolicy,
+    length: Option<usize>,
+    exclude: Vec<char>,
+}
+
+impl PasswordGenerator {
+    /// Creates a generator for passwords satisfying `policy`.
+    pub fn new(policy: PasswordPolicy) -> Self {
+        Self {
+            policy,
+            length: None,
+            exclude: Vec::new(),
+        }
+    }
+
+    /// Generates passwords of exactly `length` characters. By default the
+    /// length is 16, raised to the policy's minimum and capped at its maximum.
+    pub fn length(mut self, length: usize) -> Self {
+        self.length = Some(length);
+        self
+    }
+
+    /// Never uses the easily confused characters `0`, `O`, `1`, `l`, `I` and `|`.
+    pub fn exclude_ambiguous(self) -> Self {
+        self.exclude_chars(AMBIGUOUS.chars())
+    }
+
+    /// Never uses any of `chars`.
+    pub fn exclude_chars(mut self, chars: impl IntoIterator<Item = char>) -> Self {
+        self.exclude.extend(chars);
+        self
+    }
+
+    /// Generates a password satisfying the policy.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(RawPassword)` holding the generated password.
+    /// * `Err(TypeError::Password)` with the violation the generator could not
+    ///   avoid, e.g. when the requested length is below the policy's minimum.
+    pub fn generate(&self) -> Result<RawPassword, TypeError> {
+        let alphabet: Vec<char> = ALPHABET
+            .chars()
+            .filter(|c| !self.exclude.contains(c) && self.policy.allows(*c))
+            .collect();
+
+        let mut attempts = 1;
+        loop {
+            let result = RawPassword::parse_with(&self.policy, self.candidate(&alphabet).as_str());
+            if result.is_ok() || attempts == MAX_ATTEMPTS {
+                return result;
+            }
+            attempts += 1;
+        }
+    }
+
+    /// Returns the length of generated passwords.
+    fn target_length(&self) -> usize {
+        let (min, max) = self.policy.length_bounds();
+        self.length.unwrap_or_else(|| {
+            let length = DEFAULT_LENGTH.max(min);
+            max.map_or(length, |max| length.min(max))
+        })
+    }
+
+    /// Draws one random candidate: the required characters first, then one
+    /// character from each missing class until enough classes are present,
+    /// then random filler, all shuffled together.
+    fn candidate(&self, alphabet: &[char]) -> Zeroizing<String> {
+        let rng = &mut OsRng;
+        let of_class = |class: &CharClass| -> Vec<char> {
+            alphabet
+                .iter()
+                .copied()
+                .filter(|c| class.matches(*c))
+                .collect()
+        };
+
+        let mut chars = Zeroizing::new(Vec::new());
+        for (class, count) in self.policy.required_classes() {
+            let pool = of_class(class);
+            chars.extend((0..*count).filter_map(|_| pool.choose(rng)));
+        }
+        for class in &COUNTED_CLASSES {
+            let present = COUNTED_CLASSES
+                .iter()
+                .filter(|class| chars.iter().any(|c| class.matches(*c)))
+                .count();
+            if present >= self.policy.required_class_count() {
+                break;
+            }
+            if !chars.iter().any(|c| class.matches(*c)) {
+                chars.extend(of_class(class).choose(rng));
+            }
+        }
+        let length = self.target_length();
+        while chars.len() < length {
+            match alphabet.choose(rng) {
+                Some(c) => chars.push(*c),
+                None => break,
+            }
+        }
+
+        chars.shuffle(rng);
+        Zeroizing::new(chars.iter().collect())
+    }
+}
+
+/// ### PassphraseGenerator : Diceware-Style Passphrases
+/// Generates passphrases of random words from an embedded list of short,
+/// common English words, drawn with the operating system's CSPRNG. Each
+/// word adds log2 of the list's length in bits of entropy, about 10.3 for
+/// its 1296 words, as reported by [`PassphraseGenerator::entropy_bits`].
+/// Every generated passphrase is
+/// checked against a policy, [`PasswordPolicy::weak`] by default, before it
+/// is returned.
+///
+/// # Example
+///
+/// ```
+/// use custom_type::PassphraseGenerator;
+///
+/// let generator = PassphraseGenerator::new().words(5).separator(" ");
+/// let passphrase = generator.generate().unwrap();
+///
+/// assert_eq!(passphrase.expose_secret().split(' ').count(), 5);
+/// assert!(generator.entropy_bits() > 50.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PassphraseGenerator {
+    policy: PasswordPolicy,
+    words: usize,
+    separator: String,
+}
+
+impl Default for PassphraseGenerator {
+    /// Six words separated by `-`, satisfying [`PasswordPolicy::weak`].
+    fn default() -> Self {
+        Self {
+            policy: PasswordPolicy::weak(),
+            words: 6,
+            separator: "-".to_string(),
+        }
+    }
+}
+
+impl PassphraseGenerator {
+    /// Creates a generator of six-word passphrases separated by `-`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Checks generated passphrases against `policy` instead of
+    /// [`PasswordPolicy::weak`].
+    pub fn policy(mut self, policy: PasswordPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Sets the number of words.
+    pub fn words(mut self, words: usize) -> Self {
+        self.words = words;
+        self
+    }
+
+    /// Sets the string placed between words.
+    pub fn separator(mut self, separator: impl ToString) -> Self {
+        self.separator = separator.to_string();
+        self
+    }
+
+    /// Returns the entropy of a generated passphrase in bits.
+    pub fn entropy_bits(&self) -> f64 {
+        self.words as f64 * (wordlist().len() as f64).log2()
+    }
+
+    /// Generates a passphrase satisfying the policy.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(RawPassword)` holding the generated passphrase.
+    /// * `Err(TypeError::Password)` with the violation the generator could not
+    ///   avoid, e.g. when too few words are requested to reach the policy's
+    ///   minimum length, or the policy requires digits.
+    pub fn generate(&self) -> Result<RawPassword, TypeError> {
+        let mut attempts = 1;
+        loop {
+            let result = RawPassword::parse_with(&self.policy, self.candidate().as_str());
+            if result.is_ok() || attempts == MAX_ATTEMPTS {
+                return result;
+            }
+            attempts += 1;
+        }
+    }
+
+    /// Draws one random candidate.
+    fn candidate(&self) -> Zeroizing<String> {
+        let words: Vec<&str> = (0..self.words)
+            .filter_map(|_| wordlist().choose(&mut OsRng).copied())
+            .collect();
+        Zeroizing::new(words.join(&self.separator))
+    }
+}
+
+fn wordlist() -> &'static [&'static str] {
+    static WORDS: OnceLock<Vec<&'static str>> = OnceLock::new();
+    WORDS.get_or_init(|| parse_wordlist(WORDLIST))
+}
+
+/// Reads one word per line, skipping blank lines and any dice rolls before
+/// the word.
+fn parse_wordlist(text: &str) -> Vec<&str> {
+    text.lines()
+        .filter_map(|line| line.split_whitespace().last())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::PasswordError;
+
+    #[test]
+    fn test_generate_satisfies_policy() {
+        let policies = [
+            PasswordPolicy::weak(),
+            PasswordPolicy::medium(),
+            PasswordPolicy::strict(),
+            PasswordPolicy::new()
+                .min_length(24)
+                .require(CharClass::Digit, 4)
+                .min_classes(4),
+        ];
+        for policy in policies {
+            let generator = PasswordGenerator::new(policy.clone());
+            for _ in 0..50 {
+                let password = generator.generate().unwrap();
+                assert!(RawPassword::parse_with(&policy, password.expose_secret()).is_ok());
+            }
+        }
+    }
+
+    #[test]
+    fn test_generate_length() {
+        let generator = PasswordGenerator::new(PasswordPolicy::new().max_length(10));
+        assert_eq!(generator.generate().unwrap().expose_secret().len(), 10);
+
+        let generator = PasswordGenerator::new(PasswordPolicy::new().min_length(20));
+        assert_eq!(generator.generate().unwrap().expose_secret().len(), 20);
+
+        let generator = PasswordGenerator::new(PasswordPolicy::strict()).length(4);
+        assert_eq!(
+            generator.generate().unwrap_err(),
+            TypeError::Password(PasswordError::TooShort { min: 8, actual: 4 })
+        );
+    }
+
+    #[test]
+    fn test_generate_excludes_characters() {
+        let generator = PasswordGenerator::new(PasswordPolicy::strict().forbid_chars(['"', '\\']))
+            .exclude_ambiguous()
+            .length(64);
+        for _ in 0..20 {
+            let password = generator.generate().unwrap();
+            assert!(!password
+                .expose_secret()
+                .contains(|c| AMBIGUOUS.contains(c) || c == '"' || c == '\\'));
+        }
+    }
+
+    #[test]
+    fn test_wordlist() {
+        assert_eq!(wordlist().len(), 1296);
+        assert!(wordlist()
+            .iter()
+            .all(|word| word.len() >= 3 && word.chars().all(|c| c.is_ascii_lowercase())));
+        assert!(wordlist().windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_parse_wordlist() {
+        assert_eq!(parse_wordlist("acid\nacorn\n"), ["acid", "acorn"]);
+        assert_eq!(
+            parse_wordlist("11111\tabacus\n11112\tabdomen\n\n"),
+            ["abacus", "abdomen"]
+        );
+    }
+
+    #[test]
+    fn test_passphrase() {
+        let passphrase = PassphraseGenerator::new().generate().unwrap();
+        let words: Vec<&str> = passphrase.expose_secret().split('-').collect();
+        assert_eq!(words.len(), 6);
+        assert!(words.iter().all(|word| wordlist().contains(word)));
+
+        let generator = PassphraseGenerator::new().words(4).separator(".");
+        assert_eq!(
+            generator
+                .generate()
+                .unwrap()
+                .expose_secret()
+                .split('.')
+                .count(),
+            4
+        );
+        assert!((generator.entropy_bits() - 41.36).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_passphrase_satisfies_policy() {
+        assert!(matches!(
+            PassphraseGenerator::new()
+                .words(1)
+                .policy(PasswordPolicy::new().min_length(20))
+                .generate(),
+            Err(TypeError::Password(PasswordError::TooShort { min: 20, .. }))
+        ));
+        assert!(matches!(
+            PassphraseGenerator::new().words(0).generate(),
+            Err(TypeError::Password(PasswordError::TooShort {
+                min: 8,
+                actual: 0
+            }))
+        ));
+
+        let generator = PassphraseGenerator::new()
+            .words(2)
+            .policy(PasswordPolicy::new().min_length(10));
+        for _ in 0..20 {
+            assert!(generator.generate().unwrap().expose_secret().len() >= 10);
+        }
+        assert!(PassphraseGenerator::new()
+            .policy(PasswordPolicy::medium())
+            .generate()
+            .is_err());
+    }
+}
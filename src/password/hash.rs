@@ -0,0 +1,623 @@
+use crate::error::{HashError, TypeError};
+use crate::password::RawPassword;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// ### Hasher : Password Hashing Algorithm and Parameters
+/// Selects the algorithm used by [`RawPassword::hash`]. Every algorithm is
+/// behind a cargo feature of the same name; only `argon2` is enabled by default.
+/// Hashing with an algorithm whose feature is disabled fails with
+/// [`HashError::UnsupportedAlgorithm`].
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "argon2")]
+/// # {
+/// use custom_type::{Hasher, RawPassword};
+///
+/// let password = RawPassword::parse_strict("Valid123!").unwrap();
+/// let hashed = password.hash(&Hasher::default()).unwrap();
+/// assert!(hashed.as_str().starts_with("$argon2id$"));
+/// assert!(hashed.verify(&password));
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Hasher {
+    /// Argon2id with memory in KiB, iteration count and parallelism.
+    Argon2id {
+        memory_kib: u32,
+        iterations: u32,
+        parallelism: u32,
+    },
+    /// bcrypt with the given cost factor. Passwords over 72 bytes are
+    /// rejected, as bcrypt ignores the rest.
+    Bcrypt { cost: u32 },
+    /// scrypt with `N = 2^log_n`, block size `r` and parallelism `p`.
+    Scrypt { log_n: u8, r: u32, p: u32 },
+    /// PBKDF2 with HMAC-SHA256 and the given number of rounds.
+    Pbkdf2Sha256 { rounds: u32 },
+}
+
+impl Default for Hasher {
+    /// Argon2id with the OWASP-recommended 19 MiB, 2 iterations, 1 lane.
+    fn default() -> Self {
+        Hasher::ARGON2ID
+    }
+}
+
+impl Hasher {
+    /// Argon2id with 19 MiB of memory, 2 iterations and 1 lane.
+    pub const ARGON2ID: Hasher = Hasher::Argon2id {
+        memory_kib: 19 * 1024,
+        iterations: 2,
+        parallelism: 1,
+    };
+    /// bcrypt with cost 12.
+    pub const BCRYPT: Hasher = Hasher::Bcrypt { cost: 12 };
+    /// scrypt with `N = 2^17`, `r = 8`, `p = 1`.
+    pub const SCRYPT: Hasher = Hasher::Scrypt {
+        log_n: 17,
+        r: 8,
+        p: 1,
+    };
+    /// PBKDF2-HMAC-SHA256 with 600,000 rounds.
+    pub const PBKDF2_SHA256: Hasher = Hasher::Pbkdf2Sha256 { rounds: 600_000 };
+
+    /// Returns the name of the algorithm as it appears in a PHC string.
+    pub fn algorithm(&self) -> &'static str {
+        match self {
+            Hasher::Argon2id { .. } => "argon2id",
+            Hasher::Bcrypt { .. } => "bcrypt",
+            Hasher::Scrypt { .. } => "scrypt",
+            Hasher::Pbkdf2Sha256 { .. } => "pbkdf2-sha256",
+        }
+    }
+
+    fn hash(&self, password: &[u8]) -> Result<String, HashError> {
+        match *self {
+            #[cfg(feature = "argon2")]
+            Hasher::Argon2id {
+                memory_kib,
+                iterations,
+                parallelism,
+            } => {
+                use argon2::{Algorithm, Argon2, Params, PasswordHasher, Version};
+
+                let params = Params::new(memory_kib, iterations, parallelism, None)
+                    .map_err(|e| HashError::InvalidParams(e.to_string()))?;
+                Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+                    .hash_password(password, &salt())
+                    .map(|hash| hash.to_string())
+                    .map_err(|e| HashError::Failed(e.to_string()))
+            }
+            #[cfg(feature = "bcrypt")]
+            Hasher::Bcrypt { cost } => {
+                if password.len() > BCRYPT_MAX_BYTES {
+                    return Err(HashError::PasswordTooLong {
+                        max: BCRYPT_MAX_BYTES,
+                        actual: password.len(),
+                    });
+                }
+                bcrypt::hash(password, cost).map_err(|e| match e {
+                    bcrypt::BcryptError::CostNotAllowed(_) => {
+                        HashError::InvalidParams(e.to_string())
+                    }
+                    _ => HashError::Failed(e.to_string()),
+                })
+            }
+            #[cfg(feature = "scrypt")]
+            Hasher::Scrypt { log_n, r, p } => {
+                use scrypt::password_hash::PasswordHasher;
+
+                let params = scrypt::Params::new(log_n, r, p, scrypt::Params::RECOMMENDED_LEN)
+                    .map_err(|e| HashError::InvalidParams(e.to_string()))?;
+                scrypt::Scrypt
+                    .hash_password_customized(password, None, None, params, &salt())
+                    .map(|hash| hash.to_string())
+                    .map_err(|e| HashError::Failed(e.to_string()))
+            }
+            #[cfg(feature = "pbkdf2")]
+            Hasher::Pbkdf2Sha256 { rounds } => {
+                use pbkdf2::password_hash::PasswordHasher;
+
+                let params = pbkdf2::Params {
+                    rounds,
+                    output_length: 32,
+                };
+                pbkdf2::Pbkdf2
+                    .hash_password_customized(
+                        password,
+                        Some(pbkdf2::Algorithm::Pbkdf2Sha256.ident()),
+                        None,
+                        params,
+                        &salt(),
+                    )
+                    .map(|hash| hash.to_string())
+                    .map_err(|e| HashError::Failed(e.to_string()))
+            }
+            #[allow(unreachable_patterns)]
+            _ => {
+                let _ = password;
+                Err(HashError::UnsupportedAlgorithm(
+                    self.algorithm().to_string(),
+                ))
+            }
+        }
+    }
+}
+
+/// bcrypt only hashes the first 72 bytes of a password, so longer ones are
+/// rejected rather than silently truncated.
+#[cfg(feature = "bcrypt")]
+const BCRYPT_MAX_BYTES: usize = 72;
+
+/// The Argon2 version [`Hasher::Argon2id`] hashes with, 1.3.
+const ARGON2_VERSION: &str = "19";
+
+/// The output length in bytes [`Hasher::Pbkdf2Sha256`] hashes with.
+const PBKDF2_OUTPUT_LENGTH: &str = "32";
+
+#[cfg(any(feature = "argon2", feature = "scrypt", feature = "pbkdf2"))]
+fn salt() -> password_hash::SaltString {
+    password_hash::SaltString::generate(&mut password_hash::rand_core::OsRng)
+}
+
+/// ### HashedPassword : A Password Hash in PHC String Format
+/// Produced by [`RawPassword::hash`] and stored as a PHC string
+/// (`$argon2id$v=19$m=19456,t=2,p=1$<salt>$<hash>`), or as the modular crypt
+/// format for bcrypt (`$2b$12$...`). Serializes as that string.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "argon2")]
+/// # {
+/// use custom_type::{HashedPassword, Hasher, RawPassword};
+///
+/// let password = RawPassword::parse_strict("Valid123!").unwrap();
+/// let hashed = password.hash(&Hasher::default()).unwrap();
+///
+/// let stored = hashed.to_string();
+/// let loaded = HashedPassword::parse(stored).unwrap();
+/// assert!(loaded.verify(&password));
+/// assert!(!loaded.needs_rehash(&Hasher::default()));
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HashedPassword(String);
+
+impl fmt::Display for HashedPassword {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Serialize for HashedPassword {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for HashedPassword {
+    /// Deserializes a string and validates it with [`HashedPassword::parse`].
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let hash = String::deserialize(deserializer)?;
+        Self::parse(hash).map_err(serde::de::Error::custom)
+    }
+}
+
+impl HashedPassword {
+    /// Parses a stored PHC string (or bcrypt modular crypt string).
+    ///
+    /// # Arguments
+    ///
+    /// * `hash` - The stored hash.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` if the hash is well-formed.
+    /// * `Err(TypeError::Hash)` if it is not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use custom_type::HashedPassword;
+    ///
+    /// let hash = HashedPassword::parse("$pbkdf2-sha256$i=600000,l=32$c2FsdA$aGFzaA");
+    /// assert!(hash.is_ok());
+    ///
+    /// let invalid_hash = HashedPassword::parse("plaintext");
+    /// assert!(invalid_hash.is_err());
+    /// ```
+    pub fn parse(hash: impl ToString) -> Result<Self, TypeError> {
+        let hash = hash.to_string();
+        if !is_bcrypt(&hash) {
+            Phc::parse(&hash)?;
+        }
+        Ok(Self(hash))
+    }
+
+    /// Returns the stored hash string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the algorithm name, e.g. `"argon2id"` or `"bcrypt"`.
+    pub fn algorithm(&self) -> &str {
+        if is_bcrypt(&self.0) {
+            "bcrypt"
+        } else {
+            self.0.split('$').nth(1).unwrap_or_default()
+        }
+    }
+
+    /// Returns `true` if the algorithm's cargo feature is enabled, so that
+    /// [`HashedPassword::verify`] can check passwords against this hash.
+    pub(crate) fn is_verifiable(&self) -> bool {
+        match self.algorithm() {
+            #[cfg(feature = "bcrypt")]
+            "bcrypt" => true,
+            #[cfg(feature = "argon2")]
+            "argon2id" | "argon2i" | "argon2d" => true,
+            #[cfg(feature = "scrypt")]
+            "scrypt" => true,
+            #[cfg(feature = "pbkdf2")]
+            "pbkdf2-sha256" | "pbkdf2-sha512" | "pbkdf2" => true,
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if `password` matches this hash.
+    ///
+    /// Hashes made with an algorithm whose cargo feature is disabled never match.
+    pub fn verify(&self, password: &RawPassword) -> bool {
+        let password = password.expose_secret().as_bytes();
+        match self.algorithm() {
+            #[cfg(feature = "bcrypt")]
+            "bcrypt" => {
+                password.len() <= BCRYPT_MAX_BYTES
+                    && bcrypt::verify(password, &self.0).unwrap_or(false)
+            }
+            #[cfg(feature = "argon2")]
+            "argon2id" | "argon2i" | "argon2d" => {
+                verify_phc(&argon2::Argon2::default(), password, &self.0)
+            }
+            #[cfg(feature = "scrypt")]
+            "scrypt" => verify_phc(&scrypt::Scrypt, password, &self.0),
+            #[cfg(feature = "pbkdf2")]
+            "pbkdf2-sha256" | "pbkdf2-sha512" | "pbkdf2" => {
+                verify_phc(&pbkdf2::Pbkdf2, password, &self.0)
+            }
+            _ => {
+                let _ = password;
+                false
+            }
+        }
+    }
+
+    /// Returns the algorithm and parameters this hash was made with, if they
+    /// can be expressed as a [`Hasher`]: Argon2 hashes must use version 1.3
+    /// and PBKDF2 hashes a 32 byte output.
+    pub fn hasher(&self) -> Option<Hasher> {
+        if is_bcrypt(&self.0) {
+            let cost = self.0.get(4..6)?.parse().ok()?;
+            return Some(Hasher::Bcrypt { cost });
+        }
+        let phc = Phc::parse(&self.0).ok()?;
+        let param = |name: &str| -> Option<u32> { phc.param(name)?.parse().ok() };
+        match phc.algorithm {
+            "argon2id" if phc.version == Some(ARGON2_VERSION) => Some(Hasher::Argon2id {
+                memory_kib: param("m")?,
+                iterations: param("t")?,
+                parallelism: param("p")?,
+            }),
+            "scrypt" => Some(Hasher::Scrypt {
+                log_n: param("ln")?.try_into().ok()?,
+                r: param("r")?,
+                p: param("p")?,
+            }),
+            "pbkdf2-sha256"
+                if phc.param("l").unwrap_or(PBKDF2_OUTPUT_LENGTH) == PBKDF2_OUTPUT_LENGTH =>
+            {
+                Some(Hasher::Pbkdf2Sha256 {
+                    rounds: param("i")?,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if this hash was not made with exactly `hasher`'s
+    /// algorithm and parameters, so it should be recomputed on next login.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use custom_type::{HashedPassword, Hasher};
+    ///
+    /// let hash = HashedPassword::parse("$argon2id$v=19$m=4096,t=3,p=1$c2FsdHNhbHQ$aGFzaA").unwrap();
+    /// assert!(hash.needs_rehash(&Hasher::default()));
+    /// ```
+    pub fn needs_rehash(&self, hasher: &Hasher) -> bool {
+        self.hasher().as_ref() != Some(hasher)
+    }
+}
+
+#[cfg(any(feature = "argon2", feature = "scrypt", feature = "pbkdf2"))]
+fn verify_phc(verifier: &dyn password_hash::PasswordVerifier, password: &[u8], hash: &str) -> bool {
+    password_hash::PasswordHash::new(hash)
+        .and_then(|hash| verifier.verify_password(password, &hash))
+        .is_ok()
+}
+
+fn is_bcrypt(hash: &str) -> bool {
+    let bytes = hash.as_bytes();
+    bytes.len() == 60
+        && hash.starts_with("$2")
+        && matches!(bytes[2], b'a' | b'b' | b'x' | b'y')
+        && bytes[3] == b'$'
+        && bytes[4..6].iter().all(u8::is_ascii_digit)
+        && bytes[6] == b'$'
+        && bytes[7..]
+            .iter()
+            .all(|b| b.is_ascii_alphanumeric() || *b == b'.' || *b == b'/')
+}
+
+/// The fields of a PHC string: `$<id>[$v=<version>][$<param>=<value>(,...)][$<salt>[$<hash>]]`.
+struct Phc<'a> {
+    algorithm: &'a str,
+    version: Option<&'a str>,
+    params: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> Phc<'a> {
+    fn parse(hash: &'a str) -> Result<Self, HashError> {
+        let mut fields = hash
+            .strip_prefix('$')
+            .ok_or(HashError::InvalidFormat)?
+            .split('$')
+            .peekable();
+
+        let algorithm = fields.next().ok_or(HashError::InvalidFormat)?;
+        let is_ident = |s: &str| {
+            !s.is_empty()
+                && s.len() <= 32
+                && s.bytes()
+                    .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-')
+        };
+        if !is_ident(algorithm) {
+            return Err(HashError::InvalidFormat);
+        }
+
+        let version = fields
+            .next_if(|f| f.starts_with("v="))
+            .map(|f| &f["v=".len()..]);
+
+        let mut params = Vec::new();
+        if fields.peek().is_some_and(|f| f.contains('=')) {
+            for param in fields.next().unwrap_or_default().split(',') {
+                let (name, value) = param.split_once('=').ok_or(HashError::InvalidFormat)?;
+                if !is_ident(name) || value.is_empty() {
+                    return Err(HashError::InvalidFormat);
+                }
+                params.push((name, value));
+            }
+        }
+
+        let rest: Vec<&str> = fields.collect();
+        let is_b64 = |s: &str| {
+            !s.is_empty()
+                && s.bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'/' | b'.' | b'-'))
+        };
+        if rest.len() > 2 || !rest.iter().all(|field| is_b64(field)) {
+            return Err(HashError::InvalidFormat);
+        }
+
+        Ok(Self {
+            algorithm,
+            version,
+            params,
+        })
+    }
+
+    fn param(&self, name: &str) -> Option<&'a str> {
+        self.params
+            .iter()
+            .find(|(param, _)| *param == name)
+            .map(|(_, value)| *value)
+    }
+}
+
+impl RawPassword {
+    /// Hashes the password with `hasher` and a fresh random salt.
+    ///
+    /// # Arguments
+    ///
+    /// * `hasher` - The algorithm and parameters to use.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(HashedPassword)` holding the PHC string.
+    /// * `Err(TypeError::Hash)` if the algorithm's feature is disabled, the
+    ///   parameters are invalid or the password is too long for the algorithm.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "argon2")]
+    /// # {
+    /// use custom_type::{Hasher, RawPassword};
+    ///
+    /// let password = RawPassword::parse_strict("Valid123!").unwrap();
+    /// let hashed = password.hash(&Hasher::default()).unwrap();
+    /// assert!(hashed.verify(&password));
+    /// # }
+    /// ```
+    pub fn hash(&self, hasher: &Hasher) -> Result<HashedPassword, TypeError> {
+        let hash = hasher.hash(self.expose_secret().as_bytes())?;
+        Ok(HashedPassword(hash))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Cheap Argon2id parameters to keep tests fast.
+    #[cfg(feature = "argon2")]
+    const FAST_ARGON2ID: Hasher = Hasher::Argon2id {
+        memory_kib: 1024,
+        iterations: 1,
+        parallelism: 1,
+    };
+
+    fn password(s: &str) -> RawPassword {
+        RawPassword::parse_weak(s).unwrap()
+    }
+
+    #[test]
+    fn test_parse_hash() {
+        assert!(HashedPassword::parse(
+            "$argon2id$v=19$m=19456,t=2,p=1$c2FsdHNhbHQ$aGFzaGhhc2hoYXNo"
+        )
+        .is_ok());
+        assert!(HashedPassword::parse(
+            "$2b$12$R9h/cIPz0gi.URNNX3kh2OPST9/PgBkqquzi.Ss7KIUgO2t0jWMUW"
+        )
+        .is_ok());
+        assert_eq!(
+            HashedPassword::parse("plaintext"),
+            Err(TypeError::Hash(HashError::InvalidFormat))
+        );
+        assert_eq!(
+            HashedPassword::parse("$argon2id$m=19456,t$salt$hash"),
+            Err(TypeError::Hash(HashError::InvalidFormat))
+        );
+    }
+
+    #[test]
+    fn test_hasher_from_hash() {
+        let hash = HashedPassword::parse("$scrypt$ln=17,r=8,p=1$c2FsdA$aGFzaA").unwrap();
+        assert_eq!(hash.algorithm(), "scrypt");
+        assert_eq!(hash.hasher(), Some(Hasher::SCRYPT));
+        assert!(!hash.needs_rehash(&Hasher::SCRYPT));
+        assert!(hash.needs_rehash(&Hasher::ARGON2ID));
+
+        let hash =
+            HashedPassword::parse("$2b$10$R9h/cIPz0gi.URNNX3kh2OPST9/PgBkqquzi.Ss7KIUgO2t0jWMUW")
+                .unwrap();
+        assert_eq!(hash.hasher(), Some(Hasher::Bcrypt { cost: 10 }));
+        assert!(hash.needs_rehash(&Hasher::BCRYPT));
+
+        let hash = HashedPassword::parse("$pbkdf2-sha256$i=600000,l=64$c2FsdA$aGFzaA").unwrap();
+        assert_eq!(hash.hasher(), None);
+        assert!(hash.needs_rehash(&Hasher::PBKDF2_SHA256));
+        let hash = HashedPassword::parse("$pbkdf2-sha256$i=600000$c2FsdA$aGFzaA").unwrap();
+        assert_eq!(hash.hasher(), Some(Hasher::PBKDF2_SHA256));
+
+        let hash =
+            HashedPassword::parse("$argon2id$v=16$m=19456,t=2,p=1$c2FsdHNhbHQ$aGFzaA").unwrap();
+        assert!(hash.needs_rehash(&Hasher::ARGON2ID));
+        let hash = HashedPassword::parse("$argon2id$m=19456,t=2,p=1$c2FsdHNhbHQ$aGFzaA").unwrap();
+        assert!(hash.needs_rehash(&Hasher::ARGON2ID));
+        let hash =
+            HashedPassword::parse("$argon2id$v=19$m=19456,t=2,p=1$c2FsdHNhbHQ$aGFzaA").unwrap();
+        assert!(!hash.needs_rehash(&Hasher::ARGON2ID));
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let hash = HashedPassword::parse("$pbkdf2-sha256$i=600000,l=32$c2FsdA$aGFzaA").unwrap();
+        let json = serde_json::to_string(&hash).unwrap();
+        assert_eq!(json, "\"$pbkdf2-sha256$i=600000,l=32$c2FsdA$aGFzaA\"");
+        assert_eq!(serde_json::from_str::<HashedPassword>(&json).unwrap(), hash);
+        assert!(serde_json::from_str::<HashedPassword>("\"plaintext\"").is_err());
+    }
+
+    #[cfg(feature = "argon2")]
+    #[test]
+    fn test_argon2id() {
+        let hashed = password("validpass").hash(&FAST_ARGON2ID).unwrap();
+        assert!(hashed
+            .as_str()
+            .starts_with("$argon2id$v=19$m=1024,t=1,p=1$"));
+        assert!(hashed.verify(&password("validpass")));
+        assert!(!hashed.verify(&password("wrongpass")));
+        assert!(!hashed.needs_rehash(&FAST_ARGON2ID));
+        assert!(hashed.needs_rehash(&Hasher::ARGON2ID));
+        assert_ne!(hashed, password("validpass").hash(&FAST_ARGON2ID).unwrap());
+    }
+
+    #[cfg(feature = "bcrypt")]
+    #[test]
+    fn test_bcrypt() {
+        let hasher = Hasher::Bcrypt { cost: 4 };
+        let hashed = password("validpass").hash(&hasher).unwrap();
+        assert!(hashed.as_str().starts_with("$2b$04$"));
+        assert!(hashed.verify(&password("validpass")));
+        assert!(!hashed.verify(&password("wrongpass")));
+        assert!(!hashed.needs_rehash(&hasher));
+
+        let long = password(&"a".repeat(73));
+        assert_eq!(
+            long.hash(&hasher),
+            Err(TypeError::Hash(HashError::PasswordTooLong {
+                max: 72,
+                actual: 73
+            }))
+        );
+        let hashed = password(&"a".repeat(72)).hash(&hasher).unwrap();
+        assert!(hashed.verify(&password(&"a".repeat(72))));
+        assert!(!hashed.verify(&password(&"a".repeat(73))));
+        assert_eq!(
+            password("validpass").hash(&Hasher::Bcrypt { cost: 40 }),
+            Err(TypeError::Hash(HashError::InvalidParams(
+                "Cost needs to be between 4 and 31, got 40".to_string()
+            )))
+        );
+    }
+
+    #[cfg(feature = "scrypt")]
+    #[test]
+    fn test_scrypt() {
+        let hasher = Hasher::Scrypt {
+            log_n: 4,
+            r: 8,
+            p: 1,
+        };
+        let hashed = password("validpass").hash(&hasher).unwrap();
+        assert!(hashed.verify(&password("validpass")));
+        assert!(!hashed.verify(&password("wrongpass")));
+        assert!(!hashed.needs_rehash(&hasher));
+    }
+
+    #[cfg(feature = "pbkdf2")]
+    #[test]
+    fn test_pbkdf2() {
+        let hasher = Hasher::Pbkdf2Sha256 { rounds: 1000 };
+        let hashed = password("validpass").hash(&hasher).unwrap();
+        assert!(hashed.as_str().starts_with("$pbkdf2-sha256$i=1000,l=32$"));
+        assert!(hashed.verify(&password("validpass")));
+        assert!(!hashed.verify(&password("wrongpass")));
+        assert!(!hashed.needs_rehash(&hasher));
+    }
+
+    #[cfg(not(feature = "bcrypt"))]
+    #[test]
+    fn test_disabled_algorithm() {
+        assert_eq!(
+            password("validpass").hash(&Hasher::BCRYPT),
+            Err(TypeError::Hash(HashError::UnsupportedAlgorithm(
+                "bcrypt".to_string()
+            )))
+        );
+    }
+}
@@ -0,0 +1,283 @@
+use crate::error::{HashError, PasswordError, TypeError};
+use crate::password::{HashedPassword, RawPassword};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::time::{Duration, SystemTime};
+
+/// ### PasswordHistory : Recently Used Password Hashes
+/// Keeps the hashes of an account's last `capacity` passwords, newest first,
+/// so that [`RawPassword::check_not_reused`] can block reuse. Entries older
+/// than the optional maximum age no longer count. Serializes alongside the
+/// user record; on load, entries are put back in order and any beyond the
+/// capacity are dropped.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "argon2")]
+/// # {
+/// use custom_type::{Hasher, PasswordHistory, RawPassword};
+/// use std::time::Duration;
+///
+/// let mut history = PasswordHistory::new(4).max_age(Duration::from_secs(365 * 24 * 60 * 60));
+///
+/// let old = RawPassword::parse_strict("Valid123!").unwrap();
+/// history.push(old.hash(&Hasher::default()).unwrap());
+///
+/// assert!(old.check_not_reused(&history).is_err());
+/// let new = RawPassword::parse_strict("Another456?").unwrap();
+/// assert!(new.check_not_reused(&history).is_ok());
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "StoredHistory")]
+pub struct PasswordHistory {
+    capacity: usize,
+    max_age: Option<Duration>,
+    entries: VecDeque<HistoryEntry>,
+}
+
+/// The serialized form of [`PasswordHistory`], before its invariants are
+/// restored.
+#[derive(Deserialize)]
+struct StoredHistory {
+    capacity: usize,
+    max_age: Option<Duration>,
+    entries: VecDeque<HistoryEntry>,
+}
+
+impl From<StoredHistory> for PasswordHistory {
+    fn from(stored: StoredHistory) -> Self {
+        let mut entries = stored.entries;
+        entries
+            .make_contiguous()
+            .sort_by_key(|entry| std::cmp::Reverse(entry.set_at));
+        entries.truncate(stored.capacity);
+        Self {
+            capacity: stored.capacity,
+            max_age: stored.max_age,
+            entries,
+        }
+    }
+}
+
+/// A password hash together with when it was set.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// The hash of the password.
+    pub hash: HashedPassword,
+    /// When the password was set.
+    pub set_at: SystemTime,
+}
+
+impl PasswordHistory {
+    /// Creates an empty history remembering the last `capacity` passwords.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            max_age: None,
+            entries: VecDeque::new(),
+        }
+    }
+
+    /// Ignores passwords set longer than `max_age` ago.
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Returns how many passwords the history remembers.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Changes how many passwords the history remembers, dropping the oldest
+    /// entries if it shrinks.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.entries.truncate(capacity);
+    }
+
+    /// Records a new password set now.
+    pub fn push(&mut self, hash: HashedPassword) {
+        self.push_at(hash, SystemTime::now());
+    }
+
+    /// Records a new password set at `set_at`, keeping entries ordered newest
+    /// first and dropping any beyond the capacity.
+    pub fn push_at(&mut self, hash: HashedPassword, set_at: SystemTime) {
+        let position = self
+            .entries
+            .iter()
+            .position(|entry| entry.set_at <= set_at)
+            .unwrap_or(self.entries.len());
+        self.entries.insert(position, HistoryEntry { hash, set_at });
+        self.entries.truncate(self.capacity);
+    }
+
+    /// Returns the entries that still count against reuse, newest first.
+    pub fn recent(&self) -> impl Iterator<Item = &HistoryEntry> {
+        let now = SystemTime::now();
+        self.entries.iter().filter(move |entry| {
+            self.max_age.is_none_or(|max_age| {
+                now.duration_since(entry.set_at)
+                    .map_or(true, |age| age <= max_age)
+            })
+        })
+    }
+
+    /// Returns every stored entry, newest first, including expired ones.
+    pub fn entries(&self) -> impl Iterator<Item = &HistoryEntry> {
+        self.entries.iter()
+    }
+
+    /// Returns the number of stored entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no password has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns `true` if `password` matches a recent entry.
+    ///
+    /// Entries hashed with an algorithm whose cargo feature is disabled
+    /// cannot be checked and count as a match.
+    pub fn contains(&self, password: &RawPassword) -> bool {
+        self.recent()
+            .any(|entry| !entry.hash.is_verifiable() || entry.hash.verify(password))
+    }
+}
+
+impl RawPassword {
+    /// Checks that the password does not match a recent entry of `history`.
+    ///
+    /// # Arguments
+    ///
+    /// * `history` - The account's password history.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if the password was not used recently.
+    /// * `Err(TypeError::Password)` with [`PasswordError::Reused`] if it was.
+    /// * `Err(TypeError::Hash)` with [`HashError::UnsupportedAlgorithm`] if a
+    ///   recent entry was hashed with an algorithm whose cargo feature is
+    ///   disabled, so reuse cannot be ruled out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use custom_type::{PasswordHistory, RawPassword};
+    ///
+    /// let password = RawPassword::parse_strict("Valid123!").unwrap();
+    /// assert!(password.check_not_reused(&PasswordHistory::new(5)).is_ok());
+    /// ```
+    pub fn check_not_reused(&self, history: &PasswordHistory) -> Result<(), TypeError> {
+        if let Some(entry) = history.recent().find(|entry| !entry.hash.is_verifiable()) {
+            return Err(HashError::UnsupportedAlgorithm(entry.hash.algorithm().to_string()).into());
+        }
+        if history.contains(self) {
+            return Err(PasswordError::Reused {
+                last: history.capacity(),
+            }
+            .into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "argon2"))]
+mod tests {
+    use super::*;
+    use crate::password::Hasher;
+
+    const FAST_ARGON2ID: Hasher = Hasher::Argon2id {
+        memory_kib: 1024,
+        iterations: 1,
+        parallelism: 1,
+    };
+
+    fn hash(password: &str) -> HashedPassword {
+        RawPassword::parse_weak(password)
+            .unwrap()
+            .hash(&FAST_ARGON2ID)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_capacity() {
+        let mut history = PasswordHistory::new(2);
+        history.push(hash("first-pass"));
+        history.push(hash("second-pass"));
+        history.push(hash("third-pass"));
+        assert_eq!(history.len(), 2);
+
+        let reused = RawPassword::parse_weak("second-pass").unwrap();
+        assert_eq!(
+            reused.check_not_reused(&history),
+            Err(TypeError::Password(PasswordError::Reused { last: 2 }))
+        );
+        let dropped = RawPassword::parse_weak("first-pass").unwrap();
+        assert!(dropped.check_not_reused(&history).is_ok());
+
+        history.set_capacity(1);
+        assert!(reused.check_not_reused(&history).is_ok());
+    }
+
+    #[test]
+    fn test_max_age() {
+        let day = Duration::from_secs(24 * 60 * 60);
+        let mut history = PasswordHistory::new(5).max_age(90 * day);
+        history.push_at(hash("ancient-pass"), SystemTime::now() - 120 * day);
+        history.push_at(hash("recent-pass"), SystemTime::now() - 30 * day);
+
+        assert_eq!(history.recent().count(), 1);
+        let ancient = RawPassword::parse_weak("ancient-pass").unwrap();
+        assert!(ancient.check_not_reused(&history).is_ok());
+        let recent = RawPassword::parse_weak("recent-pass").unwrap();
+        assert!(recent.check_not_reused(&history).is_err());
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let mut history = PasswordHistory::new(3);
+        history.push(hash("first-pass"));
+        let json = serde_json::to_string(&history).unwrap();
+        let decoded: PasswordHistory = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, history);
+        assert!(decoded.contains(&RawPassword::parse_weak("first-pass").unwrap()));
+    }
+
+    #[test]
+    fn test_deserialize_trims_to_capacity() {
+        let day = Duration::from_secs(24 * 60 * 60);
+        let mut history = PasswordHistory::new(3);
+        history.push_at(hash("first-pass"), SystemTime::now() - 2 * day);
+        history.push_at(hash("second-pass"), SystemTime::now() - day);
+        let json = serde_json::to_string(&history)
+            .unwrap()
+            .replace("\"capacity\":3", "\"capacity\":1");
+
+        let decoded: PasswordHistory = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.len(), 1);
+        assert!(decoded.contains(&RawPassword::parse_weak("second-pass").unwrap()));
+        assert!(!decoded.contains(&RawPassword::parse_weak("first-pass").unwrap()));
+    }
+
+    #[test]
+    fn test_unverifiable_entry_fails_closed() {
+        let mut history = PasswordHistory::new(3);
+        history.push(HashedPassword::parse("$yescrypt$v=1$c2FsdHNhbHQ$aGFzaGhhc2g").unwrap());
+
+        let password = RawPassword::parse_weak("any-password").unwrap();
+        assert!(history.contains(&password));
+        assert_eq!(
+            password.check_not_reused(&history),
+            Err(TypeError::Hash(HashError::UnsupportedAlgorithm(
+                "yescrypt".to_string()
+            )))
+        );
+    }
+}
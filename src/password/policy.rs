@@ -0,0 +1,689 @@
+use crate::distance::levenshtein;
+use crate::error::PasswordError;
+use crate::password::breach::{self, BreachChecker};
+use crate::password::{CharClass, PasswordContext, RawPassword, Strength};
+use serde::de::{DeserializeSeed, Deserializer};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Arc;
+use unicode_segmentation::UnicodeSegmentation;
+use zeroize::Zeroizing;
+
+/// Character classes counted by [`PasswordPolicy::min_classes`].
+pub(crate) const COUNTED_CLASSES: [CharClass; 4] = [
+    CharClass::Uppercase,
+    CharClass::Lowercase,
+    CharClass::Digit,
+    CharClass::Special,
+];
+
+/// ### PasswordPolicy : Configurable Password Rules
+/// Builds the set of rules a password must satisfy. Passwords are NFKC
+/// normalized before they are checked, control characters are always
+/// rejected, and lengths are counted in Unicode code points unless
+/// [`PasswordPolicy::count_graphemes`] is set.
+///
+/// # Example
+///
+/// ```
+/// use custom_type::{CharClass, PasswordPolicy, RawPassword};
+///
+/// let policy = PasswordPolicy::new()
+///     .min_length(12)
+///     .max_length(128)
+///     .min_classes(3)
+///     .forbid_whitespace()
+///     .rule("no-company-name", |password| !password.contains("acme"));
+///
+/// assert!(RawPassword::parse_with(&policy, "Correct-horse-42").is_ok());
+/// assert!(RawPassword::parse_with(&policy, "correct horse 42").is_err());
+/// assert!(RawPassword::parse_with(&policy, "Acme-acme-1234").is_err());
+/// ```
+#[derive(Clone, Default)]
+pub struct PasswordPolicy {
+    min_length: usize,
+    max_length: Option<usize>,
+    count_graphemes: bool,
+    required: Vec<(CharClass, usize)>,
+    min_classes: Option<usize>,
+    min_strength: Option<u8>,
+    forbidden: Vec<char>,
+    forbid_whitespace: bool,
+    rules: Vec<CustomRule>,
+    forbid_context: bool,
+    min_edit_distance: Option<usize>,
+    breach_checker: Option<Arc<dyn BreachChecker>>,
+}
+
+/// A named predicate a password must satisfy.
+#[derive(Clone)]
+struct CustomRule {
+    name: String,
+    predicate: Arc<dyn Fn(&str) -> bool + Send + Sync>,
+}
+
+impl PasswordPolicy {
+    /// Creates a policy with no rules; every password without control
+    /// characters is accepted.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The policy behind [`RawPassword::parse_weak`]: at least 8 characters.
+    pub fn weak() -> Self {
+        Self::new().min_length(8)
+    }
+
+    /// The policy behind [`RawPassword::parse_medium`]: at least 8 characters
+    /// with both letters and digits.
+    pub fn medium() -> Self {
+        Self::weak()
+            .require(CharClass::Letter, 1)
+            .require(CharClass::Digit, 1)
+    }
+
+    /// The policy behind [`RawPassword::parse_strict`]: at least 8 characters
+    /// with uppercase, lowercase, digits and special characters.
+    pub fn strict() -> Self {
+        Self::weak()
+            .require(CharClass::Uppercase, 1)
+            .require(CharClass::Lowercase, 1)
+            .require(CharClass::Digit, 1)
+            .require(CharClass::Special, 1)
+    }
+
+    /// Requires at least `min` characters.
+    pub fn min_length(mut self, min: usize) -> Self {
+        self.min_length = min;
+        self
+    }
+
+    /// Allows at most `max` characters.
+    pub fn max_length(mut self, max: usize) -> Self {
+        self.max_length = Some(max);
+        self
+    }
+
+    /// Counts lengths in user-perceived characters (extended grapheme
+    /// clusters), so that an emoji built from several code points counts once.
+    pub fn count_graphemes(mut self) -> Self {
+        self.count_graphemes = true;
+        self
+    }
+
+    /// Requires at least `count` characters of `class`, replacing any earlier
+    /// requirement for the same class.
+    pub fn require(mut self, class: CharClass, count: usize) -> Self {
+        self.required.retain(|(required, _)| *required != class);
+        self.required.push((class, count));
+        self
+    }
+
+    /// Requires characters from at least `n` of the uppercase, lowercase,
+    /// digit and special classes.
+    pub fn min_classes(mut self, n: usize) -> Self {
+        self.min_classes = Some(n);
+        self
+    }
+
+    /// Requires an estimated [`Strength`] score of at least `score` (0 to 4).
+    pub fn min_strength(mut self, score: u8) -> Self {
+        self.min_strength = Some(score);
+        self
+    }
+
+    /// Rejects passwords containing any of `chars`.
+    pub fn forbid_chars(mut self, chars: impl IntoIterator<Item = char>) -> Self {
+        self.forbidden.extend(chars);
+        self
+    }
+
+    /// Rejects passwords containing whitespace.
+    pub fn forbid_whitespace(mut self) -> Self {
+        self.forbid_whitespace = true;
+        self
+    }
+
+    /// Adds a custom rule; the password is rejected when `predicate` returns `false`.
+    pub fn rule<F>(mut self, name: impl ToString, predicate: F) -> Self
+    where
+        F: Fn(&str) -> bool + Send + Sync + 'static,
+    {
+        self.rules.push(CustomRule {
+            name: name.to_string(),
+            predicate: Arc::new(predicate),
+        });
+        self
+    }
+
+    /// Rejects passwords containing any token of the [`PasswordContext`], such
+    /// as the username or the email local part, ignoring case and common l33t
+    /// substitutions.
+    pub fn forbid_context(mut self) -> Self {
+        self.forbid_context = true;
+        self
+    }
+
+    /// Requires at least `min` character edits between the password and the
+    /// [`PasswordContext`]'s previous password.
+    pub fn min_edit_distance(mut self, min: usize) -> Self {
+        self.min_edit_distance = Some(min);
+        self
+    }
+
+    /// Rejects passwords that `checker` finds in its breach corpus. A lookup
+    /// error also rejects the password, with [`PasswordError::BreachCheckFailed`].
+    pub fn breach_checker(mut self, checker: impl BreachChecker + 'static) -> Self {
+        self.breach_checker = Some(Arc::new(checker));
+        self
+    }
+
+    /// Lists every rule of the policy, in the order they are checked.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use custom_type::{PasswordPolicy, PasswordRule};
+    ///
+    /// assert_eq!(
+    ///     PasswordPolicy::weak().rules(),
+    ///     vec![PasswordRule::MinLength(8), PasswordRule::NoForbiddenCharacters]
+    /// );
+    /// ```
+    pub fn rules(&self) -> Vec<PasswordRule> {
+        let mut rules = Vec::new();
+        if self.min_length > 0 {
+            rules.push(PasswordRule::MinLength(self.min_length));
+        }
+        if let Some(max) = self.max_length {
+            rules.push(PasswordRule::MaxLength(max));
+        }
+        rules.push(PasswordRule::NoForbiddenCharacters);
+        for (class, min) in &self.required {
+            rules.push(PasswordRule::RequireClass {
+                class: *class,
+                min: *min,
+            });
+        }
+        if let Some(min) = self.min_classes {
+            rules.push(PasswordRule::MinClasses(min));
+        }
+        if let Some(score) = self.min_strength {
+            rules.push(PasswordRule::MinStrength(score));
+        }
+        for rule in &self.rules {
+            rules.push(PasswordRule::Custom(rule.name.clone()));
+        }
+        if self.forbid_context {
+            rules.push(PasswordRule::NoContextTokens);
+        }
+        if let Some(min) = self.min_edit_distance {
+            rules.push(PasswordRule::MinEditDistance(min));
+        }
+        if self.breach_checker.is_some() {
+            rules.push(PasswordRule::NotBreached);
+        }
+        rules
+    }
+
+    /// Returns the minimum and optional maximum length.
+    pub(crate) fn length_bounds(&self) -> (usize, Option<usize>) {
+        (self.min_length, self.max_length)
+    }
+
+    /// Returns the required character classes and their minimum counts.
+    pub(crate) fn required_classes(&self) -> &[(CharClass, usize)] {
+        &self.required
+    }
+
+    /// Returns how many of the counted character classes must be present.
+    pub(crate) fn required_class_count(&self) -> usize {
+        self.min_classes.unwrap_or(0)
+    }
+
+    /// Returns `true` if `c` is neither a control character nor a forbidden
+    /// character.
+    pub(crate) fn allows(&self, c: char) -> bool {
+        !(c.is_control()
+            || self.forbidden.contains(&c)
+            || (self.forbid_whitespace && c.is_whitespace()))
+    }
+
+    /// Returns the length of `password` in the policy's unit.
+    fn length(&self, password: &str) -> usize {
+        if self.count_graphemes {
+            password.graphemes(true).count()
+        } else {
+            password.chars().count()
+        }
+    }
+
+    /// Checks `password` against the rules in order, stopping at the first
+    /// violation.
+    pub(crate) fn check(
+        &self,
+        password: &str,
+        context: &PasswordContext,
+    ) -> Result<(), PasswordError> {
+        match self
+            .checks(password, context)
+            .find_map(|check| check.violation)
+        {
+            Some(violation) => Err(violation),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks `password` against every rule, recording each outcome.
+    pub(crate) fn report(&self, password: &str, context: &PasswordContext) -> PasswordReport {
+        PasswordReport {
+            checks: self.checks(password, context).collect(),
+        }
+    }
+
+    /// Checks `password` against each rule in order as the iterator advances.
+    fn checks<'a>(
+        &'a self,
+        password: &'a str,
+        context: &'a PasswordContext,
+    ) -> impl Iterator<Item = RuleCheck> + 'a {
+        let mut custom_rules = self.rules.iter();
+        self.rules().into_iter().map(move |rule| {
+            let violation = self.violation(&rule, password, context, &mut custom_rules);
+            RuleCheck { rule, violation }
+        })
+    }
+
+    /// Returns the violation of `rule` by `password`, if any. Custom rules
+    /// are taken from `custom_rules` in order, so rules sharing a name are
+    /// each checked.
+    fn violation(
+        &self,
+        rule: &PasswordRule,
+        password: &str,
+        context: &PasswordContext,
+        custom_rules: &mut std::slice::Iter<'_, CustomRule>,
+    ) -> Option<PasswordError> {
+        match rule {
+            PasswordRule::MinLength(min) => {
+                let actual = self.length(password);
+                (actual < *min).then_some(PasswordError::TooShort { min: *min, actual })
+            }
+            PasswordRule::MaxLength(max) => {
+                let actual = self.length(password);
+                (actual > *max).then_some(PasswordError::TooLong { max: *max, actual })
+            }
+            PasswordRule::NoForbiddenCharacters => password
+                .chars()
+                .enumerate()
+                .find(|(_, c)| !self.allows(*c))
+                .map(|(position, character)| PasswordError::ForbiddenCharacter {
+                    position,
+                    character,
+                }),
+            PasswordRule::RequireClass { class, min } => {
+                let actual = password.chars().filter(|c| class.matches(*c)).count();
+                (actual < *min).then_some(PasswordError::MissingClass {
+                    class: *class,
+                    min: *min,
+                    actual,
+                })
+            }
+            PasswordRule::MinClasses(min) => {
+                let actual = COUNTED_CLASSES
+                    .iter()
+                    .filter(|class| password.chars().any(|c| class.matches(c)))
+                    .count();
+                (actual < *min).then_some(PasswordError::NotEnoughClasses { min: *min, actual })
+            }
+            PasswordRule::MinStrength(min) => {
+                let actual = Strength::estimate(password).score();
+                (actual < *min).then_some(PasswordError::TooWeak { min: *min, actual })
+            }
+            PasswordRule::Custom(_) => {
+                let rule = custom_rules.next()?;
+                (!(rule.predicate)(password)).then(|| PasswordError::CustomRule(rule.name.clone()))
+            }
+            PasswordRule::NoContextTokens => context
+                .find_token(password)
+                .map(|field| PasswordError::ContainsContext { field }),
+            PasswordRule::MinEditDistance(min) => {
+                let previous = context.previous()?;
+                let actual = levenshtein(password, previous.expose_secret());
+                (actual < *min).then_some(PasswordError::TooSimilar { min: *min, actual })
+            }
+            PasswordRule::NotBreached => {
+                let checker = self.breach_checker.as_ref()?;
+                breach::violation(checker.occurrences(&breach::sha1(password)))
+            }
+        }
+    }
+}
+
+/// A single rule of a [`PasswordPolicy`], suitable for rendering a checklist.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PasswordRule {
+    /// At least this many characters.
+    MinLength(usize),
+    /// At most this many characters.
+    MaxLength(usize),
+    /// No control characters and none of the policy's forbidden characters.
+    NoForbiddenCharacters,
+    /// At least `min` characters of `class`.
+    RequireClass { class: CharClass, min: usize },
+    /// Characters from at least this many of the uppercase, lowercase, digit
+    /// and special classes.
+    MinClasses(usize),
+    /// An estimated [`Strength`] score of at least this much.
+    MinStrength(u8),
+    /// The named custom rule.
+    Custom(String),
+    /// None of the [`PasswordContext`]'s tokens.
+    NoContextTokens,
+    /// At least this many edits away from the previous password.
+    MinEditDistance(usize),
+    /// Absent from the policy's breach corpus.
+    NotBreached,
+}
+
+/// The outcome of checking a single [`PasswordRule`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleCheck {
+    /// The rule that was checked.
+    pub rule: PasswordRule,
+    /// How the password violated the rule, or `None` if it was satisfied.
+    pub violation: Option<PasswordError>,
+}
+
+/// ### PasswordReport : Every Rule Outcome at Once
+/// Produced by [`RawPassword::validate`]; lists each rule of the policy
+/// together with whether the password satisfied it.
+///
+/// # Example
+///
+/// ```
+/// use custom_type::{CharClass, PasswordPolicy, PasswordRule, RawPassword};
+///
+/// let report = RawPassword::validate(&PasswordPolicy::strict(), "short");
+/// assert!(!report.is_valid());
+/// assert_eq!(report.violations().count(), 4);
+/// assert_eq!(
+///     report.satisfied().collect::<Vec<_>>(),
+///     vec![
+///         &PasswordRule::NoForbiddenCharacters,
+///         &PasswordRule::RequireClass { class: CharClass::Lowercase, min: 1 },
+///     ]
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PasswordReport {
+    checks: Vec<RuleCheck>,
+}
+
+impl PasswordReport {
+    /// Returns `true` if every rule is satisfied.
+    pub fn is_valid(&self) -> bool {
+        self.checks.iter().all(|check| check.violation.is_none())
+    }
+
+    /// Returns the outcome of every rule, in policy order.
+    pub fn checks(&self) -> &[RuleCheck] {
+        &self.checks
+    }
+
+    /// Returns the violations, in policy order.
+    pub fn violations(&self) -> impl Iterator<Item = &PasswordError> {
+        self.checks
+            .iter()
+            .filter_map(|check| check.violation.as_ref())
+    }
+
+    /// Returns the rules the password satisfies, in policy order.
+    pub fn satisfied(&self) -> impl Iterator<Item = &PasswordRule> {
+        self.checks
+            .iter()
+            .filter(|check| check.violation.is_none())
+            .map(|check| &check.rule)
+    }
+}
+
+impl fmt::Debug for PasswordPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PasswordPolicy")
+            .field("min_length", &self.min_length)
+            .field("max_length", &self.max_length)
+            .field("count_graphemes", &self.count_graphemes)
+            .field("required", &self.required)
+            .field("min_classes", &self.min_classes)
+            .field("min_strength", &self.min_strength)
+            .field("forbidden", &self.forbidden)
+            .field("forbid_whitespace", &self.forbid_whitespace)
+            .field(
+                "rules",
+                &self.rules.iter().map(|rule| &rule.name).collect::<Vec<_>>(),
+            )
+            .field("forbid_context", &self.forbid_context)
+            .field("min_edit_distance", &self.min_edit_distance)
+            .field("breach_checker", &self.breach_checker.is_some())
+            .finish()
+    }
+}
+
+/// Deserializes a [`RawPassword`] validated against this policy, for use with
+/// `DeserializeSeed`-aware deserializers.
+impl<'de> DeserializeSeed<'de> for &PasswordPolicy {
+    type Value = RawPassword;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let password = Zeroizing::new(String::deserialize(deserializer)?);
+        RawPassword::parse_with(self, password.as_str()).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::password::ContextField;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_length_rules() {
+        let policy = PasswordPolicy::new().min_length(4).max_length(6);
+        assert_eq!(
+            policy.check("abc", &PasswordContext::new()),
+            Err(PasswordError::TooShort { min: 4, actual: 3 })
+        );
+        assert_eq!(
+            policy.check("abcdefg", &PasswordContext::new()),
+            Err(PasswordError::TooLong { max: 6, actual: 7 })
+        );
+        assert_eq!(policy.check("abcd", &PasswordContext::new()), Ok(()));
+        // Lengths are counted in characters, not bytes.
+        assert_eq!(policy.check("äöüß", &PasswordContext::new()), Ok(()));
+    }
+
+    #[test]
+    fn test_class_rules() {
+        let policy = PasswordPolicy::new().require(CharClass::Digit, 2);
+        assert_eq!(
+            policy.check("abc1", &PasswordContext::new()),
+            Err(PasswordError::MissingClass {
+                class: CharClass::Digit,
+                min: 2,
+                actual: 1
+            })
+        );
+        assert_eq!(policy.check("abc12", &PasswordContext::new()), Ok(()));
+
+        let policy = PasswordPolicy::new().min_classes(3);
+        assert_eq!(
+            policy.check("abcDEF", &PasswordContext::new()),
+            Err(PasswordError::NotEnoughClasses { min: 3, actual: 2 })
+        );
+        assert_eq!(policy.check("abcDEF1", &PasswordContext::new()), Ok(()));
+        assert_eq!(policy.check("abc!1", &PasswordContext::new()), Ok(()));
+    }
+
+    #[test]
+    fn test_forbidden_characters() {
+        let policy = PasswordPolicy::new()
+            .forbid_whitespace()
+            .forbid_chars(['"']);
+        assert_eq!(
+            policy.check("pass word", &PasswordContext::new()),
+            Err(PasswordError::ForbiddenCharacter {
+                position: 4,
+                character: ' '
+            })
+        );
+        assert_eq!(
+            policy.check("pass\"word", &PasswordContext::new()),
+            Err(PasswordError::ForbiddenCharacter {
+                position: 4,
+                character: '"'
+            })
+        );
+        assert_eq!(policy.check("password", &PasswordContext::new()), Ok(()));
+    }
+
+    #[test]
+    fn test_custom_rule() {
+        let policy = PasswordPolicy::new().rule("not-password", |p| p != "password");
+        assert_eq!(
+            policy.check("password", &PasswordContext::new()),
+            Err(PasswordError::CustomRule("not-password".to_string()))
+        );
+        assert_eq!(policy.check("passw0rd", &PasswordContext::new()), Ok(()));
+    }
+
+    #[test]
+    fn test_custom_rules_sharing_a_name() {
+        let policy = PasswordPolicy::new()
+            .rule("blocked", |p| p != "alpha")
+            .rule("blocked", |p| p != "beta");
+        assert_eq!(
+            policy.check("beta", &PasswordContext::new()),
+            Err(PasswordError::CustomRule("blocked".to_string()))
+        );
+        let report = policy.report("beta", &PasswordContext::new());
+        assert_eq!(report.checks()[1].violation, None);
+        assert_eq!(
+            report.checks()[2].violation,
+            Some(PasswordError::CustomRule("blocked".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_check_stops_at_first_violation() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        let policy = PasswordPolicy::new()
+            .min_length(8)
+            .rule("counted", move |_| {
+                counter.fetch_add(1, Ordering::SeqCst);
+                true
+            });
+        assert!(policy.check("short", &PasswordContext::new()).is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+        policy.report("short", &PasswordContext::new());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_report_lists_every_violation() {
+        let policy = PasswordPolicy::strict().max_length(10).forbid_whitespace();
+        let report = policy.report("ab cdefghijk", &PasswordContext::new());
+        assert!(!report.is_valid());
+        assert_eq!(
+            report.violations().cloned().collect::<Vec<_>>(),
+            vec![
+                PasswordError::TooLong {
+                    max: 10,
+                    actual: 12
+                },
+                PasswordError::ForbiddenCharacter {
+                    position: 2,
+                    character: ' '
+                },
+                PasswordError::MissingClass {
+                    class: CharClass::Uppercase,
+                    min: 1,
+                    actual: 0
+                },
+                PasswordError::MissingClass {
+                    class: CharClass::Digit,
+                    min: 1,
+                    actual: 0
+                },
+            ]
+        );
+        assert_eq!(
+            report.satisfied().cloned().collect::<Vec<_>>(),
+            vec![
+                PasswordRule::MinLength(8),
+                PasswordRule::RequireClass {
+                    class: CharClass::Lowercase,
+                    min: 1
+                },
+                PasswordRule::RequireClass {
+                    class: CharClass::Special,
+                    min: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_context_rules() {
+        let policy = PasswordPolicy::new().forbid_context().min_edit_distance(3);
+        let previous = RawPassword::parse_weak("Summer2023!").unwrap();
+        let context = PasswordContext::new()
+            .username("marvin")
+            .previous_password(&previous);
+
+        assert_eq!(
+            policy.check("M4RV1N-rocks", &context),
+            Err(PasswordError::ContainsContext {
+                field: ContextField::Username
+            })
+        );
+        assert_eq!(
+            policy.check("Summer2024!", &context),
+            Err(PasswordError::TooSimilar { min: 3, actual: 1 })
+        );
+        assert_eq!(policy.check("Autumn-leaves-7", &context), Ok(()));
+        // Without context there is nothing to compare against.
+        assert_eq!(policy.check("Summer2024!", &PasswordContext::new()), Ok(()));
+    }
+
+    #[test]
+    fn test_report_serializes_checklist() {
+        let report = PasswordPolicy::weak().report("short", &PasswordContext::new());
+        assert_eq!(
+            serde_json::to_value(&report).unwrap(),
+            serde_json::json!({
+                "checks": [{
+                    "rule": { "MinLength": 8 },
+                    "violation": { "TooShort": { "min": 8, "actual": 5 } }
+                }, {
+                    "rule": "NoForbiddenCharacters",
+                    "violation": null
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn test_deserialize_seed() {
+        let policy = PasswordPolicy::strict();
+        let mut deserializer = serde_json::Deserializer::from_str("\"Valid123!\"");
+        assert!(policy.deserialize(&mut deserializer).is_ok());
+
+        let mut deserializer = serde_json::Deserializer::from_str("\"validpass\"");
+        assert!(policy.deserialize(&mut deserializer).is_err());
+    }
+}
@@ -0,0 +1,997 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Embedded list of common passwords, most common first.
+const COMMON_PASSWORDS: &str = include_str!("data/common_passwords.txt");
+/// Embedded list of common English words and names, most common first.
+const ENGLISH_WORDS: &str = include_str!("data/english_words.txt");
+
+/// Passwords longer than this are only analysed up to this many characters.
+const MAX_ANALYSED_LENGTH: usize = 100;
+/// Longest token looked up in the word lists.
+const MAX_WORD_LENGTH: usize = 20;
+const BRUTEFORCE_CARDINALITY: f64 = 10.0;
+const MIN_GUESSES_BEFORE_GROWING_SEQUENCE: f64 = 10_000.0;
+const MIN_SUBMATCH_GUESSES_SINGLE_CHAR: f64 = 10.0;
+const MIN_SUBMATCH_GUESSES_MULTI_CHAR: f64 = 50.0;
+const MIN_YEAR_SPACE: f64 = 20.0;
+
+/// Keyboard rows, unshifted and shifted, used to detect keyboard walks.
+/// Rows after the first are padded by one column so that each key sits
+/// between the two keys above it.
+const QWERTY: [(&str, &str); 4] = [
+    ("`1234567890-=", "~!@#$%^&*()_+"),
+    (" qwertyuiop[]\\", " QWERTYUIOP{}|"),
+    (" asdfghjkl;'", " ASDFGHJKL:\""),
+    (" zxcvbnm,./", " ZXCVBNM<>?"),
+];
+const KEYPAD: [&str; 4] = ["789", "456", "123", " 0."];
+
+/// Common l33t substitutions, keyed by the substituted character.
+const L33T_TABLE: [(char, &[char]); 16] = [
+    ('4', &['a']),
+    ('@', &['a']),
+    ('8', &['b']),
+    ('(', &['c']),
+    ('{', &['c']),
+    ('<', &['c']),
+    ('3', &['e']),
+    ('6', &['g']),
+    ('9', &['g']),
+    ('1', &['i', 'l']),
+    ('!', &['i']),
+    ('|', &['i', 'l']),
+    ('0', &['o']),
+    ('$', &['s']),
+    ('5', &['s']),
+    ('7', &['t', 'l']),
+];
+
+/// ### Strength : Password Guessability Estimate
+/// Estimates how many guesses an attacker would need, by matching the
+/// password against common passwords, dictionary words (including reversed
+/// and l33t spellings), keyboard walks, repeats, sequences and dates.
+///
+/// # Example
+///
+/// ```
+/// use custom_type::Strength;
+///
+/// assert!(Strength::estimate("Password1!").score() <= 1);
+/// assert_eq!(Strength::estimate("correct-horse-battery-staple-91").score(), 4);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Strength {
+    score: u8,
+    guesses: f64,
+    feedback: Feedback,
+}
+
+/// Hints explaining a low [`Strength`] score.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Feedback {
+    /// Why the password is weak, if a specific reason was found.
+    pub warning: Option<String>,
+    /// Ways to make the password stronger.
+    pub suggestions: Vec<String>,
+}
+
+impl Strength {
+    /// Estimates the strength of `password`.
+    pub fn estimate(password: &str) -> Self {
+        let chars: Vec<char> = password.chars().take(MAX_ANALYSED_LENGTH).collect();
+        let (guesses, sequence) = most_guessable(&chars);
+        let score = score(guesses);
+        let feedback = feedback(score, &sequence);
+        Self {
+            score,
+            guesses,
+            feedback,
+        }
+    }
+
+    /// Returns the score from 0 (too guessable) to 4 (very unguessable).
+    pub fn score(&self) -> u8 {
+        self.score
+    }
+
+    /// Returns the estimated number of guesses needed to crack the password.
+    pub fn guesses(&self) -> f64 {
+        self.guesses
+    }
+
+    /// Returns the estimated entropy in bits, i.e. `log2(guesses)`.
+    pub fn entropy_bits(&self) -> f64 {
+        self.guesses.log2()
+    }
+
+    /// Returns hints on how to improve the password.
+    pub fn feedback(&self) -> &Feedback {
+        &self.feedback
+    }
+}
+
+fn score(guesses: f64) -> u8 {
+    const DELTA: f64 = 5.0;
+    if guesses < 1e3 + DELTA {
+        0
+    } else if guesses < 1e6 + DELTA {
+        1
+    } else if guesses < 1e8 + DELTA {
+        2
+    } else if guesses < 1e10 + DELTA {
+        3
+    } else {
+        4
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WordList {
+    Passwords,
+    English,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Pattern {
+    Dictionary {
+        list: WordList,
+        rank: usize,
+        reversed: bool,
+        l33t: bool,
+        uppercase: Uppercase,
+    },
+    Spatial,
+    Repeat,
+    Sequence,
+    Date,
+    Bruteforce,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Uppercase {
+    None,
+    First,
+    All,
+    Mixed,
+}
+
+#[derive(Debug, Clone)]
+struct Match {
+    i: usize,
+    j: usize,
+    pattern: Pattern,
+    guesses: f64,
+}
+
+impl Match {
+    fn len(&self) -> usize {
+        self.j - self.i + 1
+    }
+}
+
+fn ranked(list: &str) -> HashMap<String, usize> {
+    let mut ranks = HashMap::new();
+    for (index, word) in list
+        .lines()
+        .map(str::trim)
+        .filter(|w| !w.is_empty())
+        .enumerate()
+    {
+        ranks.entry(word.to_lowercase()).or_insert(index + 1);
+    }
+    ranks
+}
+
+fn dictionaries() -> &'static [(WordList, HashMap<String, usize>); 2] {
+    static DICTIONARIES: OnceLock<[(WordList, HashMap<String, usize>); 2]> = OnceLock::new();
+    DICTIONARIES.get_or_init(|| {
+        [
+            (WordList::Passwords, ranked(COMMON_PASSWORDS)),
+            (WordList::English, ranked(ENGLISH_WORDS)),
+        ]
+    })
+}
+
+/// Finds the decomposition of `chars` into matches that minimises the total
+/// number of guesses, returning that number and the matches used.
+fn most_guessable(chars: &[char]) -> (f64, Vec<Match>) {
+    let n = chars.len();
+    if n == 0 {
+        return (1.0, Vec::new());
+    }
+
+    let mut matches = omnimatch(chars);
+    for m in &mut matches {
+        let min = if m.len() == n {
+            1.0
+        } else if m.len() == 1 {
+            MIN_SUBMATCH_GUESSES_SINGLE_CHAR
+        } else {
+            MIN_SUBMATCH_GUESSES_MULTI_CHAR
+        };
+        m.guesses = m.guesses.max(min);
+    }
+
+    // best[k] maps a sequence length `l` to the best (guesses, product, match)
+    // for a sequence of `l` matches covering chars[..=k].
+    let mut best: Vec<HashMap<usize, (f64, f64, Match)>> = vec![HashMap::new(); n];
+
+    fn update(best: &mut [HashMap<usize, (f64, f64, Match)>], m: Match, l: usize, product: f64) {
+        let k = m.j;
+        let total = factorial(l) * product + MIN_GUESSES_BEFORE_GROWING_SEQUENCE.powi(l as i32 - 1);
+        if best[k]
+            .iter()
+            .any(|(other_l, (other_total, _, _))| *other_l <= l && *other_total <= total)
+        {
+            return;
+        }
+        best[k].insert(l, (total, product, m));
+    }
+
+    for k in 0..n {
+        for m in matches.iter().filter(|m| m.j == k) {
+            if m.i == 0 {
+                update(&mut best, m.clone(), 1, m.guesses);
+            } else {
+                let previous: Vec<(usize, f64)> = best[m.i - 1]
+                    .iter()
+                    .map(|(l, (_, product, _))| (*l, *product))
+                    .collect();
+                for (l, product) in previous {
+                    update(&mut best, m.clone(), l + 1, product * m.guesses);
+                }
+            }
+        }
+
+        let m = bruteforce(0, k);
+        let guesses = m.guesses;
+        update(&mut best, m, 1, guesses);
+        for i in 1..=k {
+            let m = bruteforce(i, k);
+            let previous: Vec<(usize, f64)> = best[i - 1]
+                .iter()
+                .filter(|(_, (_, _, last))| last.pattern != Pattern::Bruteforce)
+                .map(|(l, (_, product, _))| (*l, *product))
+                .collect();
+            for (l, product) in previous {
+                update(&mut best, m.clone(), l + 1, product * m.guesses);
+            }
+        }
+    }
+
+    let (mut l, (guesses, _, _)) = best[n - 1]
+        .iter()
+        .min_by(|a, b| a.1 .0.total_cmp(&b.1 .0))
+        .map(|(l, entry)| (*l, entry.clone()))
+        .expect("bruteforce always covers the password");
+
+    let mut sequence = Vec::new();
+    let mut k = n as isize - 1;
+    while k >= 0 {
+        let m = best[k as usize][&l].2.clone();
+        k = m.i as isize - 1;
+        l -= 1;
+        sequence.push(m);
+    }
+    sequence.reverse();
+    (guesses, sequence)
+}
+
+fn factorial(n: usize) -> f64 {
+    (1..=n).map(|i| i as f64).product()
+}
+
+fn n_choose_k(n: usize, k: usize) -> f64 {
+    if k > n {
+        return 0.0;
+    }
+    (1..=k).fold(1.0, |acc, i| acc * (n + 1 - i) as f64 / i as f64)
+}
+
+fn bruteforce(i: usize, j: usize) -> Match {
+    let len = j - i + 1;
+    let min = if len == 1 {
+        MIN_SUBMATCH_GUESSES_SINGLE_CHAR + 1.0
+    } else {
+        MIN_SUBMATCH_GUESSES_MULTI_CHAR + 1.0
+    };
+    Match {
+        i,
+        j,
+        pattern: Pattern::Bruteforce,
+        guesses: BRUTEFORCE_CARDINALITY.powi(len as i32).max(min),
+    }
+}
+
+fn omnimatch(chars: &[char]) -> Vec<Match> {
+    let mut matches = dictionary_matches(chars);
+    matches.extend(reversed_dictionary_matches(chars));
+    matches.extend(l33t_matches(chars));
+    matches.extend(spatial_matches(chars));
+    matches.extend(repeat_matches(chars));
+    matches.extend(sequence_matches(chars));
+    matches.extend(date_matches(chars));
+    matches
+}
+
+fn lowercase(chars: &[char]) -> Vec<char> {
+    chars
+        .iter()
+        .map(|c| c.to_lowercase().next().unwrap_or(*c))
+        .collect()
+}
+
+fn uppercase_variations(token: &[char]) -> (Uppercase, f64) {
+    let upper = token.iter().filter(|c| c.is_uppercase()).count();
+    let lower = token.iter().filter(|c| c.is_lowercase()).count();
+    if upper == 0 {
+        return (Uppercase::None, 1.0);
+    }
+    if lower == 0 {
+        return (Uppercase::All, 2.0);
+    }
+    let first_only = token[0].is_uppercase() && upper == 1;
+    let last_only = token[token.len() - 1].is_uppercase() && upper == 1;
+    if first_only {
+        return (Uppercase::First, 2.0);
+    }
+    if last_only {
+        return (Uppercase::Mixed, 2.0);
+    }
+    let variations = (1..=upper.min(lower))
+        .map(|k| n_choose_k(upper + lower, k))
+        .sum();
+    (Uppercase::Mixed, variations)
+}
+
+fn lookup(word: &str) -> Vec<(WordList, usize)> {
+    dictionaries()
+        .iter()
+        .filter_map(|(list, ranks)| ranks.get(word).map(|rank| (*list, *rank)))
+        .collect()
+}
+
+fn dictionary_matches(chars: &[char]) -> Vec<Match> {
+    let lower = lowercase(chars);
+    let mut matches = Vec::new();
+    for i in 0..chars.len() {
+        for j in (i + 2)..chars.len().min(i + MAX_WORD_LENGTH) {
+            let word: String = lower[i..=j].iter().collect();
+            for (list, rank) in lookup(&word) {
+                let (uppercase, variations) = uppercase_variations(&chars[i..=j]);
+                matches.push(Match {
+                    i,
+                    j,
+                    pattern: Pattern::Dictionary {
+                        list,
+                        rank,
+                        reversed: false,
+                        l33t: false,
+                        uppercase,
+                    },
+                    guesses: rank as f64 * variations,
+                });
+            }
+        }
+    }
+    matches
+}
+
+fn reversed_dictionary_matches(chars: &[char]) -> Vec<Match> {
+    let reversed: Vec<char> = chars.iter().rev().copied().collect();
+    let n = chars.len();
+    dictionary_matches(&reversed)
+        .into_iter()
+        .filter_map(|mut m| {
+            let token: String = reversed[m.i..=m.j].iter().collect();
+            // Palindromes are already found by the forward matcher.
+            if token.chars().rev().collect::<String>() == token {
+                return None;
+            }
+            let (i, j) = (n - 1 - m.j, n - 1 - m.i);
+            m.i = i;
+            m.j = j;
+            m.guesses *= 2.0;
+            if let Pattern::Dictionary { reversed, .. } = &mut m.pattern {
+                *reversed = true;
+            }
+            Some(m)
+        })
+        .collect()
+}
+
+pub(crate) fn l33t_candidates(c: char) -> Option<&'static [char]> {
+    L33T_TABLE
+        .iter()
+        .find(|(l33t, _)| *l33t == c)
+        .map(|(_, letters)| *letters)
+}
+
+fn l33t_matches(chars: &[char]) -> Vec<Match> {
+    const MAX_VARIANTS: usize = 32;
+
+    let lower = lowercase(chars);
+    let mut matches = Vec::new();
+    for i in 0..chars.len() {
+        for j in (i + 2)..chars.len().min(i + MAX_WORD_LENGTH) {
+            let token = &lower[i..=j];
+            if !token.iter().any(|c| l33t_candidates(*c).is_some())
+                || token.iter().all(|c| l33t_candidates(*c).is_some())
+            {
+                continue;
+            }
+
+            let mut variants: Vec<Vec<char>> = vec![Vec::new()];
+            for c in token {
+                let options = l33t_candidates(*c).unwrap_or(std::slice::from_ref(c));
+                variants = variants
+                    .iter()
+                    .flat_map(|prefix| {
+                        options.iter().map(move |o| {
+                            let mut next = prefix.clone();
+                            next.push(*o);
+                            next
+                        })
+                    })
+                    .take(MAX_VARIANTS)
+                    .collect();
+            }
+
+            for variant in variants {
+                let word: String = variant.iter().collect();
+                for (list, rank) in lookup(&word) {
+                    let (uppercase, variations) = uppercase_variations(&chars[i..=j]);
+                    matches.push(Match {
+                        i,
+                        j,
+                        pattern: Pattern::Dictionary {
+                            list,
+                            rank,
+                            reversed: false,
+                            l33t: true,
+                            uppercase,
+                        },
+                        guesses: rank as f64 * variations * l33t_variations(token, &variant),
+                    });
+                }
+            }
+        }
+    }
+    matches
+}
+
+fn l33t_variations(token: &[char], unsubbed: &[char]) -> f64 {
+    let mut variations = 1.0;
+    let mut seen = Vec::new();
+    for (sub, letter) in token.iter().zip(unsubbed).filter(|(s, l)| s != l) {
+        if seen.contains(&(sub, letter)) {
+            continue;
+        }
+        seen.push((sub, letter));
+        let subbed = token.iter().filter(|c| *c == sub).count();
+        let plain = token.iter().filter(|c| *c == letter).count();
+        if plain == 0 {
+            variations *= 2.0;
+        } else {
+            variations *= (1..=subbed.min(plain))
+                .map(|k| n_choose_k(subbed + plain, k))
+                .sum::<f64>();
+        }
+    }
+    variations
+}
+
+/// A keyboard layout: each character maps to its key position and whether
+/// it is typed with shift.
+struct Keyboard {
+    keys: HashMap<char, (isize, isize, bool)>,
+    slanted: bool,
+    starting_positions: f64,
+    average_degree: f64,
+}
+
+impl Keyboard {
+    fn new(rows: &[(&str, &str)], slanted: bool) -> Self {
+        let mut keys = HashMap::new();
+        for (r, (plain, shifted)) in rows.iter().enumerate() {
+            for (c, (p, s)) in plain.chars().zip(shifted.chars()).enumerate() {
+                if p == ' ' {
+                    continue;
+                }
+                keys.insert(p, (r as isize, c as isize, false));
+                if s != p {
+                    keys.insert(s, (r as isize, c as isize, true));
+                }
+            }
+        }
+        let mut keyboard = Self {
+            keys,
+            slanted,
+            starting_positions: 0.0,
+            average_degree: 0.0,
+        };
+        let positions: Vec<(isize, isize)> = keyboard
+            .keys
+            .values()
+            .filter(|(_, _, shifted)| !shifted)
+            .map(|(r, c, _)| (*r, *c))
+            .collect();
+        let degrees: usize = positions
+            .iter()
+            .map(|a| {
+                positions
+                    .iter()
+                    .filter(|b| keyboard.direction(*a, **b).is_some())
+                    .count()
+            })
+            .sum();
+        keyboard.starting_positions = positions.len() as f64;
+        keyboard.average_degree = degrees as f64 / positions.len() as f64;
+        keyboard
+    }
+
+    fn direction(&self, a: (isize, isize), b: (isize, isize)) -> Option<(isize, isize)> {
+        let delta = (b.0 - a.0, b.1 - a.1);
+        let adjacent = if self.slanted {
+            matches!(
+                delta,
+                (0, -1) | (0, 1) | (-1, 0) | (-1, 1) | (1, -1) | (1, 0)
+            )
+        } else {
+            delta != (0, 0) && delta.0.abs() <= 1 && delta.1.abs() <= 1
+        };
+        adjacent.then_some(delta)
+    }
+
+    fn guesses(&self, length: usize, turns: usize, shifted: usize) -> f64 {
+        let mut guesses = 0.0;
+        for i in 2..=length {
+            for j in 1..=turns.min(i - 1) {
+                guesses += n_choose_k(i - 1, j - 1)
+                    * self.starting_positions
+                    * self.average_degree.powi(j as i32);
+            }
+        }
+        let unshifted = length - shifted;
+        if shifted > 0 {
+            if unshifted == 0 {
+                guesses *= 2.0;
+            } else {
+                guesses *= (1..=shifted.min(unshifted))
+                    .map(|k| n_choose_k(shifted + unshifted, k))
+                    .sum::<f64>();
+            }
+        }
+        guesses
+    }
+}
+
+fn keyboards() -> &'static [Keyboard; 2] {
+    static KEYBOARDS: OnceLock<[Keyboard; 2]> = OnceLock::new();
+    KEYBOARDS.get_or_init(|| {
+        let keypad: Vec<(&str, &str)> = KEYPAD.iter().map(|row| (*row, *row)).collect();
+        [Keyboard::new(&QWERTY, true), Keyboard::new(&keypad, false)]
+    })
+}
+
+fn spatial_matches(chars: &[char]) -> Vec<Match> {
+    let mut matches = Vec::new();
+    for keyboard in keyboards() {
+        let mut i = 0;
+        while i + 1 < chars.len() {
+            let mut j = i;
+            let mut last_direction = None;
+            let mut turns = 0;
+            while j + 1 < chars.len() {
+                let (Some(a), Some(b)) = (
+                    keyboard.keys.get(&chars[j]),
+                    keyboard.keys.get(&chars[j + 1]),
+                ) else {
+                    break;
+                };
+                let Some(direction) = keyboard.direction((a.0, a.1), (b.0, b.1)) else {
+                    break;
+                };
+                if last_direction != Some(direction) {
+                    turns += 1;
+                    last_direction = Some(direction);
+                }
+                j += 1;
+            }
+            if j - i + 1 >= 3 {
+                let shifted = chars[i..=j]
+                    .iter()
+                    .filter(|c| keyboard.keys.get(c).is_some_and(|k| k.2))
+                    .count();
+                matches.push(Match {
+                    i,
+                    j,
+                    pattern: Pattern::Spatial,
+                    guesses: keyboard.guesses(j - i + 1, turns, shifted),
+                });
+            }
+            i = if j > i { j } else { i + 1 };
+        }
+    }
+    matches
+}
+
+fn repeat_matches(chars: &[char]) -> Vec<Match> {
+    let n = chars.len();
+    let mut matches = Vec::new();
+    let mut i = 0;
+    while i < n {
+        let mut best: Option<(usize, usize)> = None;
+        for base_len in 1..=(n - i) / 2 {
+            let base = &chars[i..i + base_len];
+            let mut count = 1;
+            while i + (count + 1) * base_len <= n
+                && &chars[i + count * base_len..i + (count + 1) * base_len] == base
+            {
+                count += 1;
+            }
+            let long_enough = count >= 3 || (count >= 2 && base_len > 1);
+            if long_enough && best.is_none_or(|(b, c)| base_len * count > b * c) {
+                best = Some((base_len, count));
+            }
+        }
+        match best {
+            Some((base_len, count)) => {
+                let (base_guesses, _) = most_guessable(&chars[i..i + base_len]);
+                matches.push(Match {
+                    i,
+                    j: i + base_len * count - 1,
+                    pattern: Pattern::Repeat,
+                    guesses: base_guesses * count as f64,
+                });
+                i += base_len * count;
+            }
+            None => i += 1,
+        }
+    }
+    matches
+}
+
+fn sequence_matches(chars: &[char]) -> Vec<Match> {
+    const MAX_DELTA: i64 = 5;
+
+    fn class(c: char) -> Option<u8> {
+        if c.is_ascii_lowercase() {
+            Some(0)
+        } else if c.is_ascii_uppercase() {
+            Some(1)
+        } else if c.is_ascii_digit() {
+            Some(2)
+        } else {
+            None
+        }
+    }
+
+    let n = chars.len();
+    let mut matches = Vec::new();
+    let mut i = 0;
+    while i + 2 < n {
+        let delta = chars[i + 1] as i64 - chars[i] as i64;
+        let same_class = |a: char, b: char| class(a).is_some() && class(a) == class(b);
+        if delta == 0 || delta.abs() > MAX_DELTA || !same_class(chars[i], chars[i + 1]) {
+            i += 1;
+            continue;
+        }
+        let mut j = i + 1;
+        while j + 1 < n
+            && chars[j + 1] as i64 - chars[j] as i64 == delta
+            && same_class(chars[j], chars[j + 1])
+        {
+            j += 1;
+        }
+        if j - i + 1 >= 3 {
+            let first = chars[i];
+            let mut base = if "aAzZ019".contains(first) {
+                4.0
+            } else if first.is_ascii_digit() {
+                10.0
+            } else {
+                26.0
+            };
+            if delta < 0 {
+                base *= 2.0;
+            }
+            matches.push(Match {
+                i,
+                j,
+                pattern: Pattern::Sequence,
+                guesses: base * (j - i + 1) as f64,
+            });
+            i = j;
+        } else {
+            i += 1;
+        }
+    }
+    matches
+}
+
+fn reference_year() -> i64 {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    1970 + (seconds / 31_556_952) as i64
+}
+
+fn year_guesses(year: i64) -> f64 {
+    ((year - reference_year()).abs() as f64).max(MIN_YEAR_SPACE)
+}
+
+/// Interprets a one-to-four digit year, expanding two-digit years.
+fn expand_year(year: i64, digits: usize) -> Option<i64> {
+    match digits {
+        2 if year > 50 => Some(1900 + year),
+        2 => Some(2000 + year),
+        4 if (1000..=2050).contains(&year) => Some(year),
+        _ => None,
+    }
+}
+
+fn valid_date(day: i64, month: i64) -> bool {
+    (1..=12).contains(&month) && (1..=31).contains(&day)
+}
+
+fn date_matches(chars: &[char]) -> Vec<Match> {
+    static SEPARATED: OnceLock<Regex> = OnceLock::new();
+    let separated = SEPARATED.get_or_init(|| {
+        Regex::new(r"^(\d{1,4})([\s/\\_.-])(\d{1,2})([\s/\\_.-])(\d{1,4})$").unwrap()
+    });
+
+    let n = chars.len();
+    let mut matches = Vec::new();
+    for i in 0..n {
+        for j in (i + 3)..n.min(i + 10) {
+            let token: String = chars[i..=j].iter().collect();
+            let guesses = if token.chars().all(|c| c.is_ascii_digit()) {
+                unseparated_date(&token)
+            } else {
+                separated.captures(&token).and_then(|captures| {
+                    if captures[2] != captures[4] {
+                        return None;
+                    }
+                    let parts = [&captures[1], &captures[3], &captures[5]];
+                    date_from_parts(&parts).map(|g| g * 4.0)
+                })
+            };
+            if let Some(guesses) = guesses {
+                matches.push(Match {
+                    i,
+                    j,
+                    pattern: Pattern::Date,
+                    guesses,
+                });
+            }
+        }
+    }
+    matches
+}
+
+fn unseparated_date(token: &str) -> Option<f64> {
+    if token.len() == 4 {
+        let year: i64 = token.parse().ok()?;
+        if (1900..=2099).contains(&year) {
+            return Some(year_guesses(year));
+        }
+    }
+    if token.len() > 8 {
+        return None;
+    }
+    let mut best: Option<f64> = None;
+    for first in 1..token.len() {
+        for second in (first + 1)..token.len() {
+            let parts = [&token[..first], &token[first..second], &token[second..]];
+            if let Some(guesses) = date_from_parts(&parts) {
+                best = Some(best.map_or(guesses, |b: f64| b.min(guesses)));
+            }
+        }
+    }
+    best
+}
+
+/// Tries year-month-day, day-month-year and month-day-year orders.
+fn date_from_parts(parts: &[&str; 3]) -> Option<f64> {
+    let values: Vec<(i64, usize)> = parts
+        .iter()
+        .map(|p| p.parse().ok().map(|v| (v, p.len())))
+        .collect::<Option<_>>()?;
+    let orders = [(0, 1, 2), (2, 1, 0), (2, 0, 1)];
+    orders
+        .iter()
+        .filter_map(|&(y, m, d)| {
+            let (year, year_digits) = values[y];
+            let (month, month_digits) = values[m];
+            let (day, day_digits) = values[d];
+            if month_digits > 2 || day_digits > 2 || !valid_date(day, month) {
+                return None;
+            }
+            expand_year(year, year_digits).map(|year| year_guesses(year) * 365.0)
+        })
+        .reduce(f64::min)
+}
+
+fn feedback(score: u8, sequence: &[Match]) -> Feedback {
+    const DEFAULT: [&str; 2] = [
+        "Use a few words, avoid common phrases",
+        "No need for symbols, digits, or uppercase letters",
+    ];
+
+    if sequence.is_empty() {
+        return Feedback {
+            warning: None,
+            suggestions: DEFAULT.iter().map(|s| s.to_string()).collect(),
+        };
+    }
+    if score > 2 {
+        return Feedback::default();
+    }
+
+    let longest = sequence
+        .iter()
+        .max_by_key(|m| m.len())
+        .expect("sequence is not empty");
+    let mut feedback = match_feedback(longest, sequence.len() == 1);
+    feedback.suggestions.insert(
+        0,
+        "Add another word or two. Uncommon words are better.".to_string(),
+    );
+    feedback
+}
+
+fn match_feedback(m: &Match, sole: bool) -> Feedback {
+    let (warning, suggestions): (Option<&str>, Vec<&str>) = match &m.pattern {
+        Pattern::Dictionary {
+            list,
+            rank,
+            reversed,
+            l33t,
+            uppercase,
+        } => {
+            let warning = match list {
+                WordList::Passwords if sole && !l33t && !reversed => Some(if *rank <= 10 {
+                    "This is a top-10 common password"
+                } else if *rank <= 100 {
+                    "This is a top-100 common password"
+                } else {
+                    "This is a very common password"
+                }),
+                WordList::Passwords if m.guesses.log10() <= 4.0 => {
+                    Some("This is similar to a commonly used password")
+                }
+                WordList::English if sole => Some("A word by itself is easy to guess"),
+                _ => None,
+            };
+            let mut suggestions = Vec::new();
+            match uppercase {
+                Uppercase::First => suggestions.push("Capitalization doesn't help very much"),
+                Uppercase::All => {
+                    suggestions.push("All-uppercase is almost as easy to guess as all-lowercase")
+                }
+                _ => {}
+            }
+            if *reversed && m.len() >= 4 {
+                suggestions.push("Reversed words aren't much harder to guess");
+            }
+            if *l33t {
+                suggestions
+                    .push("Predictable substitutions like '@' instead of 'a' don't help very much");
+            }
+            (warning, suggestions)
+        }
+        Pattern::Spatial => (
+            Some("Short keyboard patterns are easy to guess"),
+            vec!["Use a longer keyboard pattern with more turns"],
+        ),
+        Pattern::Repeat => (
+            Some("Repeats like \"abcabcabc\" are only slightly harder to guess than \"abc\""),
+            vec!["Avoid repeated words and characters"],
+        ),
+        Pattern::Sequence => (
+            Some("Sequences like abc or 6543 are easy to guess"),
+            vec!["Avoid sequences"],
+        ),
+        Pattern::Date => (
+            Some("Dates are often easy to guess"),
+            vec!["Avoid dates and years that are associated with you"],
+        ),
+        Pattern::Bruteforce => (None, Vec::new()),
+    };
+    Feedback {
+        warning: warning.map(str::to_string),
+        suggestions: suggestions.into_iter().map(str::to_string).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_common_passwords_score_zero() {
+        for password in ["password", "123456", "qwerty", "iloveyou", "dragon"] {
+            assert_eq!(Strength::estimate(password).score(), 0, "{}", password);
+        }
+        let strength = Strength::estimate("password");
+        assert_eq!(
+            strength.feedback().warning.as_deref(),
+            Some("This is a top-10 common password")
+        );
+    }
+
+    #[test]
+    fn test_predictable_patterns_score_low() {
+        for password in [
+            "Password1!",
+            "P@ssw0rd",
+            "qwertyuiop",
+            "zxcvbnm,./",
+            "abcdefgh",
+            "98765432",
+            "aaaaaaaaaaaa",
+            "abcabcabcabc",
+            "1987-06-23",
+            "drowssap",
+        ] {
+            assert!(
+                Strength::estimate(password).score() <= 1,
+                "{} scored {}",
+                password,
+                Strength::estimate(password).score()
+            );
+        }
+    }
+
+    #[test]
+    fn test_pattern_feedback() {
+        assert_eq!(
+            Strength::estimate("p@ssw0rd")
+                .feedback()
+                .suggestions
+                .last()
+                .map(String::as_str),
+            Some("Predictable substitutions like '@' instead of 'a' don't help very much")
+        );
+        assert_eq!(
+            Strength::estimate("sdfghjkl").feedback().warning.as_deref(),
+            Some("Short keyboard patterns are easy to guess")
+        );
+        assert_eq!(
+            Strength::estimate("1987-06-23")
+                .feedback()
+                .warning
+                .as_deref(),
+            Some("Dates are often easy to guess")
+        );
+    }
+
+    #[test]
+    fn test_strong_passwords_score_high() {
+        for password in [
+            "correct-horse-battery-staple-91",
+            "vT7#kq!2Lm9xWz",
+            "Xq3$tZ8wPf2!",
+        ] {
+            assert!(Strength::estimate(password).score() >= 3, "{}", password);
+        }
+        assert_eq!(
+            Strength::estimate("vT7#kq!2Lm9xWz").feedback(),
+            &Feedback::default()
+        );
+    }
+
+    #[test]
+    fn test_guesses_and_entropy() {
+        let strength = Strength::estimate("password");
+        assert!(strength.guesses() < 10.0);
+        assert!((strength.entropy_bits() - strength.guesses().log2()).abs() < f64::EPSILON);
+        assert_eq!(Strength::estimate("").score(), 0);
+    }
+}
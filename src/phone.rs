@@ -1,8 +1,19 @@
 use derive_more::Display;
-use regex::Regex;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::{error::TypeError, CountryCode};
+use crate::{
+    error::{PhoneError, TypeError},
+    CountryCode,
+};
+
+mod format;
+mod metadata;
+mod number_type;
+mod policy;
+
+pub use format::{AsYouTypeFormatter, PhoneFormat};
+pub use number_type::NumberType;
+pub use policy::PhonePolicy;
 
 /// This crate provides functionality to parse and validate phone numbers with country codes.
 ///
@@ -11,23 +22,63 @@ use crate::{error::TypeError, CountryCode};
 /// ```
 /// use custom_type::{PhoneNumber, CountryCode};
 ///
-///     let phone_number = PhoneNumber::parse(CountryCode::USA, "1234567890").unwrap();
+///     let phone_number = PhoneNumber::parse(CountryCode::USA, "2015550123").unwrap();
 ///     println!("{}", phone_number);
 /// ```
 ///
 /// # Features
 ///
 /// - Parse and validate phone numbers with a specified country code.
+/// - Validate numbers against each country's numbering plan, from embedded
+///   libphonenumber metadata.
+/// - Parse free-form input with separators, `+`/`00` prefixes and `tel:`
+///   URIs, inferring the country from the calling code.
+/// - Format numbers in E.164, international, national and RFC 3966 styles,
+///   or as they are typed.
+/// - Classify numbers as mobile, fixed-line, toll-free and so on, and accept
+///   only some types at parse time.
 /// - Custom error type `TypeError` for handling invalid phone numbers.
 /// ### PhoneNumber : Parse `impl ToString` Into a Valid Phone Number
 /// Provides a method to parse and validate phone numbers with specified country codes.
-#[derive(Debug, PartialEq, Display, Serialize, Deserialize)]
-pub struct PhoneNumber(String);
+#[derive(Debug, PartialEq, Display)]
+#[display(fmt = "{}{}", country_code, national_number)]
+pub struct PhoneNumber {
+    country_code: CountryCode,
+    national_number: String,
+}
+
+impl Serialize for PhoneNumber {
+    /// Serializes the number in E.164 form, `+<country code><number>`.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for PhoneNumber {
+    /// Deserializes a `+<country code><number>` string, as produced by `Serialize`,
+    /// infers the country and validates it with [`PhoneNumber::parse`].
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let phone_number = String::deserialize(deserializer)?;
+        Self::parse_e164(&phone_number).map_err(serde::de::Error::custom)
+    }
+}
 
 impl PhoneNumber {
     /// Parses a given string into a phone number with the specified country code.
     ///
-    /// A valid phone number must be between 10 to 15 digits long.
+    /// The number is given in national form and must fit the numbering plan
+    /// of `country_code`: one of its national number lengths and its pattern
+    /// of valid numbers. Singapore numbers have 8 digits and French ones 9,
+    /// for example. Countries the crate has no metadata for accept any number
+    /// that fits in E.164.
+    ///
+    /// A leading trunk prefix, such as the `0` in `081234567890` in Indonesia
+    /// or `07400123456` in the UK, is dropped so the number is stored in
+    /// E.164 form. Countries without a trunk prefix, such as Italy, keep
+    /// every digit.
     ///
     /// # Arguments
     ///
@@ -37,36 +88,306 @@ impl PhoneNumber {
     /// # Returns
     ///
     /// * `Ok(Self)` if the phone number meets the criteria.
-    /// * `Err(TypeError::ParseError)` if the phone number is invalid.
+    /// * `Err(TypeError::Phone)` if the phone number is invalid.
     ///
     /// # Examples
     ///
     /// ```
     /// use custom_type::{PhoneNumber, CountryCode};
     ///
-    /// let phone_number = PhoneNumber::parse(CountryCode::USA, "1234567890");
+    /// let phone_number = PhoneNumber::parse(CountryCode::SGP, "81234567");
     /// assert!(phone_number.is_ok());
     ///
-    /// let invalid_phone_number = PhoneNumber::parse(CountryCode::USA, "12345");
+    /// let invalid_phone_number = PhoneNumber::parse(CountryCode::SGP, "123456789012345");
     /// assert!(invalid_phone_number.is_err());
+    ///
+    /// let phone_number = PhoneNumber::parse(CountryCode::INA, "081234567890").unwrap();
+    /// assert_eq!(phone_number.to_string(), "+6281234567890");
     /// ```
     pub fn parse(
         country_code: CountryCode,
         phone_number: impl ToString,
     ) -> Result<Self, TypeError> {
         let phone_number = phone_number.to_string();
-        let phone_regex = Regex::new(r"^[0-9]{10,15}$").unwrap();
 
-        if phone_regex.is_match(&phone_number) {
-            Ok(Self(format!("{}{}", country_code, phone_number)))
+        if let Some((position, character)) = phone_number
+            .chars()
+            .enumerate()
+            .find(|(_, c)| !c.is_ascii_digit())
+        {
+            return Err(PhoneError::InvalidCharacter {
+                position,
+                character,
+            }
+            .into());
+        }
+
+        let metadata = metadata::get(country_code);
+        let phone_number = match metadata {
+            Some(metadata) => metadata.strip_trunk_prefix(&phone_number).to_string(),
+            None => phone_number,
+        };
+        let (min, max) = match metadata {
+            Some(metadata) => (metadata.min_length(), metadata.max_length()),
+            None => (MIN_DIGITS, MAX_DIGITS - country_code.calling_code().len()),
+        };
+
+        let digits = phone_number.len();
+        if digits < min {
+            Err(PhoneError::TooShort {
+                min,
+                actual: digits,
+            }
+            .into())
+        } else if digits > max {
+            Err(PhoneError::TooLong {
+                max,
+                actual: digits,
+            }
+            .into())
+        } else if metadata.is_some_and(|metadata| !metadata.is_valid(&phone_number)) {
+            Err(PhoneError::InvalidNumber { country_code }.into())
         } else {
-            Err(TypeError::ParseError(
-                "unable to parse phone number, invalid phone number.".to_string(),
-            ))
+            Ok(Self {
+                country_code,
+                national_number: phone_number,
+            })
         }
     }
+
+    /// Parses a number in international form from free-form input, inferring
+    /// the country from its calling code.
+    ///
+    /// Spaces, `-`, `.`, `/` and parentheses between digits are ignored. The
+    /// number starts with `+`, or with the `00` or `011` international
+    /// dialling prefix, and may be a `tel:` URI whose parameters are ignored.
+    /// Countries sharing a calling code, such as the USA and Canada on +1 or
+    /// Russia and Kazakhstan on +7, are told apart by the number's leading
+    /// digits.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The number as typed or pasted.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` if the number is valid for the country it belongs to.
+    /// * `Err(TypeError::Phone)` if the input has no international prefix,
+    ///   an unknown calling code or an invalid number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use custom_type::{CountryCode, PhoneNumber};
+    ///
+    /// let phone_number = PhoneNumber::parse_international("+62 812-3456-7890").unwrap();
+    /// assert_eq!(phone_number.country_code(), CountryCode::INA);
+    /// assert_eq!(phone_number.to_string(), "+6281234567890");
+    ///
+    /// let phone_number = PhoneNumber::parse_international("tel:+1-416-555-0123").unwrap();
+    /// assert_eq!(phone_number.country_code(), CountryCode::CAN);
+    ///
+    /// assert!(PhoneNumber::parse_international("0044 20 7946 0958").is_ok());
+    /// assert!(PhoneNumber::parse_international("(201) 555-0123").is_err());
+    /// ```
+    pub fn parse_international(input: impl ToString) -> Result<Self, TypeError> {
+        let (international, digits) = strip_formatting(&input.to_string())?;
+        if international {
+            return Self::parse_calling_code(&digits);
+        }
+        let digits = INTERNATIONAL_PREFIXES
+            .iter()
+            .find_map(|prefix| digits.strip_prefix(prefix))
+            .ok_or(PhoneError::MissingCountryCode)?;
+        Self::parse_calling_code(digits)
+    }
+
+    /// Parses free-form input in national form for `region`, or in
+    /// international form.
+    ///
+    /// Input is cleaned up as in [`PhoneNumber::parse_international`]. A
+    /// number starting with `+` or with the international dialling prefix
+    /// of `region`, such as `011` in the USA or `810` in Russia, is parsed
+    /// as international; any other number is parsed for `region` with
+    /// [`PhoneNumber::parse`].
+    ///
+    /// # Arguments
+    ///
+    /// * `region` - The country to assume for numbers in national form.
+    /// * `input` - The number as typed or pasted.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` if the number is valid.
+    /// * `Err(TypeError::Phone)` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use custom_type::{CountryCode, PhoneNumber};
+    ///
+    /// let phone_number =
+    ///     PhoneNumber::parse_with_default_region(CountryCode::USA, "(201) 555-0123").unwrap();
+    /// assert_eq!(phone_number.to_string(), "+12015550123");
+    ///
+    /// let phone_number =
+    ///     PhoneNumber::parse_with_default_region(CountryCode::USA, "011 44 20 7946 0958").unwrap();
+    /// assert_eq!(phone_number.country_code(), CountryCode::UK);
+    /// ```
+    pub fn parse_with_default_region(
+        region: CountryCode,
+        input: impl ToString,
+    ) -> Result<Self, TypeError> {
+        let (international, digits) = strip_formatting(&input.to_string())?;
+        if international {
+            return Self::parse_calling_code(&digits);
+        }
+        let international_number = match metadata::get(region) {
+            Some(metadata) => metadata.strip_international_prefix(&digits),
+            None => digits.strip_prefix("00"),
+        };
+        match international_number {
+            Some(digits) => Self::parse_calling_code(digits),
+            None => Self::parse(region, digits),
+        }
+    }
+
+    /// Parses free-form input into a number satisfying `policy`.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - How to read the input and the number types to accept.
+    /// * `input` - The number as typed or pasted.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` if the number is valid and of an accepted type.
+    /// * `Err(TypeError::Phone)` with the first failed check otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use custom_type::{PhoneNumber, PhonePolicy};
+    ///
+    /// let policy = PhonePolicy::new().mobile_only();
+    /// assert!(PhoneNumber::parse_with_policy(&policy, "+44 7400 123456").is_ok());
+    /// assert!(PhoneNumber::parse_with_policy(&policy, "+44 20 7946 0958").is_err());
+    /// ```
+    pub fn parse_with_policy(
+        policy: &PhonePolicy,
+        input: impl ToString,
+    ) -> Result<Self, TypeError> {
+        policy.parse(&input.to_string())
+    }
+
+    /// Returns the country the number belongs to.
+    pub fn country_code(&self) -> CountryCode {
+        self.country_code
+    }
+
+    /// Returns the national significant number, the digits after the
+    /// calling code.
+    pub fn national_number(&self) -> &str {
+        &self.national_number
+    }
+
+    /// Parses a `+<country code><number>` string.
+    fn parse_e164(phone_number: &str) -> Result<Self, TypeError> {
+        let digits = phone_number
+            .strip_prefix('+')
+            .ok_or(PhoneError::MissingCountryCode)?;
+        Self::parse_calling_code(digits)
+    }
+
+    /// Parses `digits`, a calling code followed by a national number, for
+    /// the country the number belongs to.
+    fn parse_calling_code(digits: &str) -> Result<Self, TypeError> {
+        let (country_code, national_number) =
+            split_calling_code(digits).ok_or(PhoneError::UnknownCountryCode)?;
+        Self::parse(country_code, national_number)
+    }
+}
+
+/// Splits `digits` into the country of its calling code and the national
+/// number. Among countries sharing the calling code, the first declared one
+/// is used unless another claims the number.
+fn split_calling_code(digits: &str) -> Option<(CountryCode, &str)> {
+    for length in 1..=3 {
+        let calling_code = digits.get(..length)?;
+        let candidates: Vec<CountryCode> = CountryCode::ALL
+            .into_iter()
+            .filter(|country_code| country_code.calling_code() == calling_code)
+            .collect();
+        if let Some(&main) = candidates.first() {
+            let national_number = &digits[length..];
+            let country_code = candidates[1..]
+                .iter()
+                .copied()
+                .find(|country_code| {
+                    metadata::get(*country_code)
+                        .is_some_and(|metadata| metadata.claims(national_number))
+                })
+                .unwrap_or(main);
+            return Some((country_code, national_number));
+        }
+    }
+    None
 }
 
+/// International dialling prefixes recognized without a default region:
+/// `00`, used by most countries, and `011`, used across the +1 region.
+const INTERNATIONAL_PREFIXES: [&str; 2] = ["00", "011"];
+
+/// Characters that may separate the digits of free-form input.
+const SEPARATORS: [char; 6] = [' ', '-', '.', '/', '(', ')'];
+
+/// Removes a `tel:` scheme and its parameters, and the separators between
+/// digits.
+///
+/// # Returns
+///
+/// * `Ok((bool, String))` with whether the number starts with `+`, and its
+///   digits.
+/// * `Err(PhoneError::InvalidCharacter)` at the first character that is
+///   neither a digit nor a separator, counted in characters from the start
+///   of `input`.
+fn strip_formatting(input: &str) -> Result<(bool, String), PhoneError> {
+    let trimmed = input.trim_start();
+    let mut offset = input.len() - trimmed.len();
+    let mut number = trimmed.trim_end();
+    if number
+        .get(..4)
+        .is_some_and(|scheme| scheme.eq_ignore_ascii_case("tel:"))
+    {
+        offset += 4;
+        number = number[4..].split(';').next().unwrap_or_default();
+    }
+    let offset = input[..offset].chars().count();
+
+    let mut international = false;
+    let mut digits = String::new();
+    for (position, character) in number.chars().enumerate() {
+        match character {
+            '0'..='9' => digits.push(character),
+            '+' if position == 0 => international = true,
+            _ if SEPARATORS.contains(&character) => {}
+            _ => {
+                return Err(PhoneError::InvalidCharacter {
+                    position: offset + position,
+                    character,
+                })
+            }
+        }
+    }
+    Ok((international, digits))
+}
+
+/// Minimum number of digits accepted after the country code, for countries
+/// without numbering-plan metadata.
+const MIN_DIGITS: usize = 4;
+/// Maximum number of digits in an E.164 number, country code included.
+const MAX_DIGITS: usize = 15;
+
 /// ======================================================================
 /// ========================= Unit Test
 /// ======================================================================
@@ -74,23 +395,78 @@ impl PhoneNumber {
 mod tests {
     use super::*;
 
+    fn number(country_code: CountryCode, national_number: &str) -> PhoneNumber {
+        PhoneNumber {
+            country_code,
+            national_number: national_number.to_string(),
+        }
+    }
+
     #[test]
     fn test_valid_phone_number() {
         assert_eq!(
-            PhoneNumber::parse(CountryCode::USA, "1234567890"),
-            Ok(PhoneNumber("+11234567890".to_string()))
+            PhoneNumber::parse(CountryCode::USA, "2015550123"),
+            Ok(number(CountryCode::USA, "2015550123"))
+        );
+        assert_eq!(
+            PhoneNumber::parse(CountryCode::UK, "2079460958"),
+            Ok(number(CountryCode::UK, "2079460958"))
+        );
+        assert_eq!(
+            PhoneNumber::parse(CountryCode::IND, "9123456789"),
+            Ok(number(CountryCode::IND, "9123456789"))
+        );
+        assert_eq!(
+            PhoneNumber::parse(CountryCode::AUS, "212345678"),
+            Ok(number(CountryCode::AUS, "212345678"))
+        );
+        assert_eq!(
+            PhoneNumber::parse(CountryCode::SGP, "81234567"),
+            Ok(number(CountryCode::SGP, "81234567"))
+        );
+        assert_eq!(
+            PhoneNumber::parse(CountryCode::FRA, "612345678"),
+            Ok(number(CountryCode::FRA, "612345678"))
+        );
+        assert_eq!(
+            PhoneNumber::parse(CountryCode::VAT, "12345678"),
+            Ok(number(CountryCode::VAT, "12345678"))
+        );
+    }
+
+    #[test]
+    fn test_trunk_prefix() {
+        assert_eq!(
+            PhoneNumber::parse(CountryCode::INA, "081234567890"),
+            Ok(number(CountryCode::INA, "81234567890"))
+        );
+        assert_eq!(
+            PhoneNumber::parse(CountryCode::UK, "07400123456"),
+            Ok(number(CountryCode::UK, "7400123456"))
+        );
+        assert_eq!(
+            PhoneNumber::parse(CountryCode::DEU, "015123456789"),
+            Ok(number(CountryCode::DEU, "15123456789"))
         );
         assert_eq!(
-            PhoneNumber::parse(CountryCode::UK, "123456789012"),
-            Ok(PhoneNumber("+44123456789012".to_string()))
+            PhoneNumber::parse(CountryCode::RUS, "89123456789"),
+            Ok(number(CountryCode::RUS, "9123456789"))
         );
         assert_eq!(
-            PhoneNumber::parse(CountryCode::IND, "1234567890"),
-            Ok(PhoneNumber("+911234567890".to_string()))
+            PhoneNumber::parse(CountryCode::USA, "12015550123"),
+            Ok(number(CountryCode::USA, "2015550123"))
         );
         assert_eq!(
-            PhoneNumber::parse(CountryCode::AUS, "1234567890"),
-            Ok(PhoneNumber("+611234567890".to_string()))
+            PhoneNumber::parse(CountryCode::ITA, "0612345678"),
+            Ok(number(CountryCode::ITA, "0612345678"))
+        );
+        assert_eq!(
+            PhoneNumber::parse_international("+44 (0)20 7946 0958"),
+            Ok(number(CountryCode::UK, "2079460958"))
+        );
+        assert_eq!(
+            PhoneNumber::parse_with_default_region(CountryCode::FRA, "06 12 34 56 78"),
+            Ok(number(CountryCode::FRA, "612345678"))
         );
     }
 
@@ -98,21 +474,147 @@ mod tests {
     fn test_invalid_phone_number() {
         assert_eq!(
             PhoneNumber::parse(CountryCode::USA, "12345"),
-            Err(TypeError::ParseError(
-                "unable to parse phone number, invalid phone number.".to_string()
-            ))
+            Err(TypeError::Phone(PhoneError::TooShort {
+                min: 10,
+                actual: 5
+            }))
         );
         assert_eq!(
             PhoneNumber::parse(CountryCode::UK, "phone123456"),
-            Err(TypeError::ParseError(
-                "unable to parse phone number, invalid phone number.".to_string()
-            ))
+            Err(TypeError::Phone(PhoneError::InvalidCharacter {
+                position: 0,
+                character: 'p'
+            }))
         );
         assert_eq!(
             PhoneNumber::parse(CountryCode::IND, "123-456-7890"),
-            Err(TypeError::ParseError(
-                "unable to parse phone number, invalid phone number.".to_string()
-            ))
+            Err(TypeError::Phone(PhoneError::InvalidCharacter {
+                position: 3,
+                character: '-'
+            }))
+        );
+        assert_eq!(
+            PhoneNumber::parse(CountryCode::SGP, "123456789012345"),
+            Err(TypeError::Phone(PhoneError::TooLong {
+                max: 11,
+                actual: 15
+            }))
+        );
+        assert_eq!(
+            PhoneNumber::parse(CountryCode::SGP, "123456789"),
+            Err(TypeError::Phone(PhoneError::InvalidNumber {
+                country_code: CountryCode::SGP
+            }))
+        );
+        assert_eq!(
+            PhoneNumber::parse(CountryCode::USA, "1234567890"),
+            Err(TypeError::Phone(PhoneError::InvalidNumber {
+                country_code: CountryCode::USA
+            }))
+        );
+        assert_eq!(
+            PhoneNumber::parse(CountryCode::FRA, "61234567"),
+            Err(TypeError::Phone(PhoneError::TooShort { min: 9, actual: 8 }))
+        );
+    }
+
+    #[test]
+    fn test_deserialize_phone_number() {
+        assert_eq!(
+            serde_json::from_str::<PhoneNumber>("\"+6281234567890\"").unwrap(),
+            number(CountryCode::INA, "81234567890")
+        );
+        assert_eq!(
+            serde_json::from_str::<PhoneNumber>("\"+442079460958\"").unwrap(),
+            number(CountryCode::UK, "2079460958")
+        );
+        assert!(serde_json::from_str::<PhoneNumber>("\"2015550123\"").is_err());
+        assert_eq!(
+            PhoneNumber::parse_e164("2015550123"),
+            Err(TypeError::Phone(PhoneError::MissingCountryCode))
+        );
+        assert!(serde_json::from_str::<PhoneNumber>("\"+1123\"").is_err());
+        assert!(serde_json::from_str::<PhoneNumber>("\"+11234567890\"").is_err());
+        assert_eq!(
+            serde_json::to_string(&number(CountryCode::CAN, "4165550123")).unwrap(),
+            "\"+14165550123\""
+        );
+    }
+
+    #[test]
+    fn test_parse_international() {
+        assert_eq!(
+            PhoneNumber::parse_international("+62 812-3456-7890"),
+            Ok(number(CountryCode::INA, "81234567890"))
+        );
+        assert_eq!(
+            PhoneNumber::parse_international("0044 20 7946 0958"),
+            Ok(number(CountryCode::UK, "2079460958"))
+        );
+        assert_eq!(
+            PhoneNumber::parse_international("011 (65) 8123.4567"),
+            Ok(number(CountryCode::SGP, "81234567"))
+        );
+        assert_eq!(
+            PhoneNumber::parse_international(" TEL:+1-201-555-0123;ext=42 "),
+            Ok(number(CountryCode::USA, "2015550123"))
+        );
+        assert_eq!(
+            PhoneNumber::parse_international("(201) 555-0123"),
+            Err(TypeError::Phone(PhoneError::MissingCountryCode))
+        );
+        assert_eq!(
+            PhoneNumber::parse_international("+1 201 555 0123 x42"),
+            Err(TypeError::Phone(PhoneError::InvalidCharacter {
+                position: 16,
+                character: 'x'
+            }))
+        );
+        assert_eq!(
+            PhoneNumber::parse_international("+80012345678"),
+            Err(TypeError::Phone(PhoneError::UnknownCountryCode))
+        );
+    }
+
+    #[test]
+    fn test_shared_calling_codes() {
+        assert_eq!(
+            PhoneNumber::parse_international("+1 416 555 0123"),
+            Ok(number(CountryCode::CAN, "4165550123"))
+        );
+        assert_eq!(
+            PhoneNumber::parse_international("+7 701 234 5678"),
+            Ok(number(CountryCode::KAZ, "7012345678"))
+        );
+        assert_eq!(
+            PhoneNumber::parse_international("+7 912 345-67-89"),
+            Ok(number(CountryCode::RUS, "9123456789"))
+        );
+        assert_eq!(
+            PhoneNumber::parse_international("+1 123 456 7890"),
+            Err(TypeError::Phone(PhoneError::InvalidNumber {
+                country_code: CountryCode::USA
+            }))
+        );
+    }
+
+    #[test]
+    fn test_parse_with_default_region() {
+        assert_eq!(
+            PhoneNumber::parse_with_default_region(CountryCode::USA, "(201) 555-0123"),
+            Ok(number(CountryCode::USA, "2015550123"))
+        );
+        assert_eq!(
+            PhoneNumber::parse_with_default_region(CountryCode::RUS, "810 44 20 7946 0958"),
+            Ok(number(CountryCode::UK, "2079460958"))
+        );
+        assert_eq!(
+            PhoneNumber::parse_with_default_region(CountryCode::FRA, "+33 6 12 34 56 78"),
+            Ok(number(CountryCode::FRA, "612345678"))
+        );
+        assert_eq!(
+            PhoneNumber::parse_with_default_region(CountryCode::SGP, "8123 4567"),
+            Ok(number(CountryCode::SGP, "81234567"))
         );
     }
 }
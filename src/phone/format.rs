@@ -0,0 +1,351 @@
+use crate::phone::metadata::{self, Metadata, NumberFormat};
+use crate::phone::split_calling_code;
+use crate::{CountryCode, PhoneNumber};
+use regex::Captures;
+
+/// ### PhoneFormat : Phone Number Display Styles
+/// The ways [`PhoneNumber::format`] can write a number, shown here for
+/// `+6281234567890`.
+///
+/// - `E164`: `+6281234567890`, for storage and SMS gateways.
+/// - `International`: `+62 812-3456-7890`, for display to anyone.
+/// - `National`: `0812-3456-7890`, as dialled within the country.
+/// - `Rfc3966`: `tel:+62-812-3456-7890`, for `tel:` links.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PhoneFormat {
+    E164,
+    International,
+    National,
+    Rfc3966,
+}
+
+impl PhoneNumber {
+    /// Writes the number in the given style, grouping its digits the way its
+    /// country does.
+    ///
+    /// # Arguments
+    ///
+    /// * `format` - The style to write the number in.
+    ///
+    /// # Returns
+    ///
+    /// The formatted number. Numbers no grouping applies to are written
+    /// without separators.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use custom_type::{CountryCode, PhoneFormat, PhoneNumber};
+    ///
+    /// let phone_number = PhoneNumber::parse(CountryCode::INA, "081234567890").unwrap();
+    /// assert_eq!(phone_number.format(PhoneFormat::E164), "+6281234567890");
+    /// assert_eq!(phone_number.format(PhoneFormat::International), "+62 812-3456-7890");
+    /// assert_eq!(phone_number.format(PhoneFormat::National), "0812-3456-7890");
+    /// assert_eq!(phone_number.format(PhoneFormat::Rfc3966), "tel:+62-812-3456-7890");
+    /// ```
+    pub fn format(&self, format: PhoneFormat) -> String {
+        let metadata = metadata::get(self.country_code);
+        match format {
+            PhoneFormat::E164 => self.to_string(),
+            PhoneFormat::International => {
+                let number = metadata
+                    .and_then(|metadata| {
+                        format_number(metadata, &self.national_number, |format| {
+                            format.international.map(str::to_string)
+                        })
+                    })
+                    .unwrap_or_else(|| self.national_number.clone());
+                format!("{} {}", self.country_code, number)
+            }
+            PhoneFormat::National => metadata
+                .and_then(|metadata| {
+                    format_number(metadata, &self.national_number, |format| {
+                        Some(format.national_with_trunk_prefix())
+                    })
+                })
+                .unwrap_or_else(|| self.national_number.clone()),
+            PhoneFormat::Rfc3966 => {
+                let international = self.format(PhoneFormat::International);
+                let groups: Vec<&str> = international
+                    .split(|c: char| !c.is_ascii_digit() && c != '+')
+                    .filter(|group| !group.is_empty())
+                    .collect();
+                format!("tel:{}", groups.join("-"))
+            }
+        }
+    }
+}
+
+/// ### AsYouTypeFormatter : Format Phone Numbers While They Are Typed
+/// Formats a number one character at a time, for input fields. Numbers
+/// starting with `+` are formatted internationally for the country of their
+/// calling code; others are formatted nationally for the default region,
+/// with the trunk prefix if one is typed. Characters other than digits and
+/// a leading `+` are ignored.
+///
+/// # Example
+///
+/// ```
+/// use custom_type::{AsYouTypeFormatter, CountryCode};
+///
+/// let mut formatter = AsYouTypeFormatter::new(CountryCode::INA);
+/// let typed: Vec<String> = "08123".chars().map(|c| formatter.input_digit(c)).collect();
+/// assert_eq!(typed, ["0", "08", "081", "0812", "0812-3"]);
+///
+/// formatter.clear();
+/// for c in "+1201555".chars() {
+///     formatter.input_digit(c);
+/// }
+/// assert_eq!(formatter.input_digit('0'), "+1 201-555-0");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AsYouTypeFormatter {
+    region: CountryCode,
+    input: String,
+}
+
+impl AsYouTypeFormatter {
+    /// Creates a formatter for numbers typed in `region`.
+    pub fn new(region: CountryCode) -> Self {
+        Self {
+            region,
+            input: String::new(),
+        }
+    }
+
+    /// Adds a typed character and returns the number formatted so far.
+    pub fn input_digit(&mut self, character: char) -> String {
+        if character.is_ascii_digit() || (character == '+' && self.input.is_empty()) {
+            self.input.push(character);
+        }
+        self.output()
+    }
+
+    /// Forgets the characters typed so far.
+    pub fn clear(&mut self) {
+        self.input.clear();
+    }
+
+    fn output(&self) -> String {
+        if let Some(digits) = self.input.strip_prefix('+') {
+            let Some((country_code, national_number)) = split_calling_code(digits) else {
+                return self.input.clone();
+            };
+            if national_number.is_empty() {
+                return country_code.to_string();
+            }
+            let number = metadata::get(country_code)
+                .and_then(|metadata| {
+                    format_partial(metadata, national_number, |format| {
+                        format.international.map(str::to_string)
+                    })
+                })
+                .unwrap_or_else(|| national_number.to_string());
+            return format!("{} {}", country_code, number);
+        }
+
+        let Some(metadata) = metadata::get(self.region) else {
+            return self.input.clone();
+        };
+        let trunk_prefix = metadata.trunk_prefix;
+        let national_number = trunk_prefix.and_then(|prefix| self.input.strip_prefix(prefix));
+        let number = match (trunk_prefix, national_number) {
+            (Some(trunk_prefix), Some(national_number)) => {
+                format_partial(metadata, national_number, |format| {
+                    Some(match format.trunk_prefix_rule {
+                        Some(_) => format.national_with_trunk_prefix(),
+                        None => format!("{} {}", trunk_prefix, format.national),
+                    })
+                })
+            }
+            _ => format_partial(metadata, &self.input, |format| {
+                Some(format.national.to_string())
+            }),
+        };
+        number.unwrap_or_else(|| self.input.clone())
+    }
+}
+
+/// Formats `national_number` with the first format of the country that
+/// matches it and has a template.
+fn format_number(
+    metadata: &Metadata,
+    national_number: &str,
+    template: impl Fn(&NumberFormat) -> Option<String>,
+) -> Option<String> {
+    metadata.formats.iter().find_map(|format| {
+        if !format.applies_to(national_number) {
+            return None;
+        }
+        let captures = format.pattern.captures(national_number)?;
+        Some(expand(&template(format)?, &captures, national_number.len()))
+    })
+}
+
+/// Formats the first `digits` of a number still being typed, with the first
+/// format of the country that matches some completion of it up to a
+/// possible length.
+fn format_partial(
+    metadata: &Metadata,
+    digits: &str,
+    template: impl Fn(&NumberFormat) -> Option<String>,
+) -> Option<String> {
+    if digits.is_empty() {
+        return None;
+    }
+    metadata.formats.iter().find_map(|format| {
+        if !format.applies_to(digits) {
+            return None;
+        }
+        let template = template(format)?;
+        metadata
+            .lengths
+            .iter()
+            .filter(|&&length| length >= digits.len())
+            .flat_map(|&length| {
+                ('0'..='9').map(move |filler| {
+                    let mut number = digits.to_string();
+                    number.extend(std::iter::repeat_n(filler, length - digits.len()));
+                    number
+                })
+            })
+            .find_map(|number| {
+                let captures = format.pattern.captures(&number)?;
+                Some(expand(&template, &captures, digits.len()))
+            })
+    })
+}
+
+/// Writes the groups of `captures` into `template` in place of `$1`, `$2`…,
+/// keeping only the first `typed` digits of the number and cutting what
+/// follows the last of them.
+fn expand(template: &str, captures: &Captures, typed: usize) -> String {
+    let mut formatted = String::new();
+    let mut end = 0;
+    let mut characters = template.chars().peekable();
+    while let Some(character) = characters.next() {
+        let group = match character {
+            '$' => characters.peek().and_then(|digit| digit.to_digit(10)),
+            _ => None,
+        };
+        let Some(group) = group else {
+            formatted.push(character);
+            continue;
+        };
+        characters.next();
+        if let Some(digits) = captures.get(group as usize) {
+            for (index, digit) in digits.as_str().char_indices() {
+                if digits.start() + index < typed {
+                    formatted.push(digit);
+                    end = formatted.len();
+                }
+            }
+        }
+    }
+    if typed < captures[0].len() {
+        formatted.truncate(end);
+    }
+    formatted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn typed(region: CountryCode, input: &str) -> Vec<String> {
+        let mut formatter = AsYouTypeFormatter::new(region);
+        input.chars().map(|c| formatter.input_digit(c)).collect()
+    }
+
+    #[test]
+    fn test_format() {
+        let indonesia = PhoneNumber::parse(CountryCode::INA, "081234567890").unwrap();
+        assert_eq!(indonesia.format(PhoneFormat::E164), "+6281234567890");
+        assert_eq!(
+            indonesia.format(PhoneFormat::International),
+            "+62 812-3456-7890"
+        );
+        assert_eq!(indonesia.format(PhoneFormat::National), "0812-3456-7890");
+        assert_eq!(
+            indonesia.format(PhoneFormat::Rfc3966),
+            "tel:+62-812-3456-7890"
+        );
+
+        let usa = PhoneNumber::parse(CountryCode::USA, "2015550123").unwrap();
+        assert_eq!(usa.format(PhoneFormat::International), "+1 201-555-0123");
+        assert_eq!(usa.format(PhoneFormat::National), "(201) 555-0123");
+        assert_eq!(usa.format(PhoneFormat::Rfc3966), "tel:+1-201-555-0123");
+
+        let uk = PhoneNumber::parse(CountryCode::UK, "02079460958").unwrap();
+        assert_eq!(uk.format(PhoneFormat::International), "+44 20 7946 0958");
+        assert_eq!(uk.format(PhoneFormat::National), "020 7946 0958");
+
+        let italy = PhoneNumber::parse(CountryCode::ITA, "0612345678").unwrap();
+        assert_eq!(italy.format(PhoneFormat::International), "+39 06 1234 5678");
+        assert_eq!(italy.format(PhoneFormat::National), "06 1234 5678");
+
+        let vatican = PhoneNumber::parse(CountryCode::VAT, "12345678").unwrap();
+        assert_eq!(vatican.format(PhoneFormat::International), "+379 12345678");
+        assert_eq!(vatican.format(PhoneFormat::Rfc3966), "tel:+379-12345678");
+    }
+
+    #[test]
+    fn test_as_you_type_national() {
+        assert_eq!(
+            typed(CountryCode::INA, "081234567890"),
+            [
+                "0",
+                "08",
+                "081",
+                "0812",
+                "0812-3",
+                "0812-34",
+                "0812-345",
+                "0812-345-6",
+                "0812-345-67",
+                "0812-345-678",
+                "0812-3456-789",
+                "0812-3456-7890",
+            ]
+        );
+        assert_eq!(
+            typed(CountryCode::USA, "2015550123").last().unwrap(),
+            "(201) 555-0123"
+        );
+        assert_eq!(
+            typed(CountryCode::USA, "12015550123").last().unwrap(),
+            "1 (201) 555-0123"
+        );
+    }
+
+    #[test]
+    fn test_as_you_type_international() {
+        assert_eq!(
+            typed(CountryCode::USA, "+4420794"),
+            [
+                "+",
+                "+4",
+                "+44",
+                "+44 2",
+                "+44 20",
+                "+44 20 7",
+                "+44 20 79",
+                "+44 20 794"
+            ]
+        );
+        assert_eq!(
+            typed(CountryCode::USA, "+1 (416) 555-0123").last().unwrap(),
+            "+1 416-555-0123"
+        );
+        assert_eq!(typed(CountryCode::USA, "+999").last().unwrap(), "+999");
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut formatter = AsYouTypeFormatter::new(CountryCode::FRA);
+        formatter.input_digit('0');
+        formatter.input_digit('6');
+        formatter.clear();
+        assert_eq!(formatter.input_digit('+'), "+");
+    }
+}
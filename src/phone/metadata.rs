@@ -0,0 +1,276 @@
+use crate::CountryCode;
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Numbering plans derived from libphonenumber's `PhoneNumberMetadata.xml`
+/// (version 9.0.21, Apache License 2.0), taking the main territory of each
+/// calling code.
+///
+/// Each line is tab separated and starts with the `CountryCode` variant and
+/// a record kind. A `number` record holds the calling code, the national
+/// trunk prefix, the possible national number lengths, the leading digits
+/// that identify the country within a shared calling code, the pattern of
+/// international dialling prefixes and the general pattern of national
+/// numbers. Each `type` record holds a number type, such as `mobile` or
+/// `toll_free`, and the pattern of its numbers. Each `format`
+/// record that follows holds the leading digits and pattern of the numbers
+/// it applies to, the national and international templates, where `$1`,
+/// `$2`… stand for the pattern's groups and `NA` means the format is only
+/// used nationally, and the template that replaces `$1` in national form to
+/// add the trunk prefix. `-` marks an empty field.
+const METADATA: &str = include_str!("data/metadata.txt");
+
+/// The numbering plan of one country.
+#[derive(Debug)]
+pub(crate) struct Metadata {
+    pub(crate) trunk_prefix: Option<&'static str>,
+    pub(crate) lengths: Vec<usize>,
+    leading_digits: Option<Regex>,
+    international_prefix: Regex,
+    pattern: Regex,
+    types: Vec<(&'static str, Regex)>,
+    pub(crate) formats: Vec<NumberFormat>,
+}
+
+/// How numbers starting with some leading digits are grouped.
+#[derive(Debug)]
+pub(crate) struct NumberFormat {
+    leading_digits: Option<Regex>,
+    pub(crate) pattern: Regex,
+    pub(crate) national: &'static str,
+    pub(crate) international: Option<&'static str>,
+    pub(crate) trunk_prefix_rule: Option<&'static str>,
+}
+
+impl NumberFormat {
+    /// Returns `true` if numbers starting with `digits` may use this format.
+    pub(crate) fn applies_to(&self, digits: &str) -> bool {
+        self.leading_digits
+            .as_ref()
+            .is_none_or(|leading_digits| leading_digits.is_match(digits))
+    }
+
+    /// Returns the national template with the trunk prefix added to the
+    /// first group, if the country writes it.
+    pub(crate) fn national_with_trunk_prefix(&self) -> String {
+        match self.trunk_prefix_rule {
+            Some(rule) => self.national.replacen("$1", rule, 1),
+            None => self.national.to_string(),
+        }
+    }
+}
+
+impl Metadata {
+    fn parse(lines: &[&'static str]) -> Self {
+        let mut metadata: Option<Self> = None;
+        for line in lines {
+            let fields: Vec<&'static str> = line.split('\t').collect();
+            match fields[1..] {
+                ["number", _, trunk_prefix, lengths, leading_digits, idd, pattern] => {
+                    metadata = Some(Self {
+                        trunk_prefix: optional(trunk_prefix),
+                        lengths: lengths
+                            .split(',')
+                            .map(|length| length.parse().expect("invalid length"))
+                            .collect(),
+                        leading_digits: optional(leading_digits)
+                            .map(|digits| compile(&format!("^(?:{})", digits))),
+                        international_prefix: compile(&format!("^(?:{})", idd)),
+                        pattern: compile(&format!("^(?:{})$", pattern)),
+                        types: Vec::new(),
+                        formats: Vec::new(),
+                    })
+                }
+                ["type", name, pattern] => metadata
+                    .as_mut()
+                    .expect("type record before number record")
+                    .types
+                    .push((name, compile(&format!("^(?:{})$", pattern)))),
+                ["format", leading_digits, pattern, national, international, rule] => {
+                    let format = NumberFormat {
+                        leading_digits: optional(leading_digits)
+                            .map(|digits| compile(&format!("^(?:{})", digits))),
+                        pattern: compile(&format!("^(?:{})$", pattern)),
+                        national,
+                        international: (international != "NA").then_some(international),
+                        trunk_prefix_rule: optional(rule),
+                    };
+                    metadata
+                        .as_mut()
+                        .expect("format record before number record")
+                        .formats
+                        .push(format);
+                }
+                _ => panic!("invalid phone metadata line: {}", line),
+            }
+        }
+        metadata.expect("missing number record")
+    }
+
+    /// Returns the fewest digits a national number can have.
+    pub(crate) fn min_length(&self) -> usize {
+        self.lengths.iter().copied().min().unwrap_or(0)
+    }
+
+    /// Returns the most digits a national number can have.
+    pub(crate) fn max_length(&self) -> usize {
+        self.lengths.iter().copied().max().unwrap_or(0)
+    }
+
+    /// Returns `true` if `national_number` belongs to this country rather
+    /// than another sharing its calling code: it starts with the country's
+    /// leading digits, or is valid here if the country has none.
+    pub(crate) fn claims(&self, national_number: &str) -> bool {
+        match &self.leading_digits {
+            Some(leading_digits) => leading_digits.is_match(national_number),
+            None => self.is_valid(national_number),
+        }
+    }
+
+    /// Removes the international dialling prefix, such as `00` or `011`,
+    /// from the start of `digits`. Calling codes never start with `0`, so
+    /// nothing is removed if the rest would.
+    pub(crate) fn strip_international_prefix<'a>(&self, digits: &'a str) -> Option<&'a str> {
+        let prefix = self.international_prefix.find(digits)?;
+        let rest = &digits[prefix.end()..];
+        (!rest.is_empty() && !rest.starts_with('0')).then_some(rest)
+    }
+
+    /// Removes the trunk prefix, such as the `0` dialled before national
+    /// numbers in the UK, from the start of `national_number` if what
+    /// remains is a valid number. Countries without one, such as Italy where
+    /// the leading `0` is part of the number, keep every digit.
+    pub(crate) fn strip_trunk_prefix<'a>(&self, national_number: &'a str) -> &'a str {
+        match self
+            .trunk_prefix
+            .and_then(|trunk_prefix| national_number.strip_prefix(trunk_prefix))
+        {
+            Some(number) if self.is_valid(number) => number,
+            _ => national_number,
+        }
+    }
+
+    /// Returns `true` if `national_number` has a possible length, matches
+    /// the country's general pattern and is a number of some type.
+    pub(crate) fn is_valid(&self, national_number: &str) -> bool {
+        self.lengths.contains(&national_number.len())
+            && self.pattern.is_match(national_number)
+            && self
+                .types
+                .iter()
+                .any(|(_, pattern)| pattern.is_match(national_number))
+    }
+
+    /// Returns `true` if `national_number` matches the pattern of the number
+    /// type called `name`, such as `mobile`.
+    pub(crate) fn is_type(&self, name: &str, national_number: &str) -> bool {
+        self.types
+            .iter()
+            .any(|(type_name, pattern)| *type_name == name && pattern.is_match(national_number))
+    }
+}
+
+fn optional(field: &'static str) -> Option<&'static str> {
+    (field != "-").then_some(field)
+}
+
+fn compile(pattern: &str) -> Regex {
+    Regex::new(pattern).expect("invalid phone metadata pattern")
+}
+
+struct Entry {
+    lines: Vec<&'static str>,
+    metadata: OnceLock<Metadata>,
+}
+
+/// Returns the numbering plan of `country_code`, or `None` if the crate has
+/// none. Patterns are compiled the first time a country is used.
+pub(crate) fn get(country_code: CountryCode) -> Option<&'static Metadata> {
+    static ENTRIES: OnceLock<HashMap<CountryCode, Entry>> = OnceLock::new();
+    let entries = ENTRIES.get_or_init(|| {
+        let names: HashMap<String, CountryCode> = CountryCode::ALL
+            .into_iter()
+            .map(|country_code| (format!("{:?}", country_code), country_code))
+            .collect();
+        let mut entries = HashMap::new();
+        for line in METADATA.lines() {
+            let name = line.split('\t').next().unwrap_or_default();
+            let country_code = *names
+                .get(name)
+                .unwrap_or_else(|| panic!("unknown country in phone metadata: {}", name));
+            entries
+                .entry(country_code)
+                .or_insert_with(|| Entry {
+                    lines: Vec::new(),
+                    metadata: OnceLock::new(),
+                })
+                .lines
+                .push(line);
+        }
+        entries
+    });
+    let entry = entries.get(&country_code)?;
+    Some(entry.metadata.get_or_init(|| Metadata::parse(&entry.lines)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_country_parses() {
+        for country_code in CountryCode::ALL {
+            match get(country_code) {
+                Some(metadata) => assert!(metadata.min_length() > 0, "{:?}", country_code),
+                None => assert_eq!(country_code, CountryCode::VAT),
+            }
+        }
+        for line in METADATA.lines().filter(|line| line.contains("\tnumber\t")) {
+            let fields: Vec<&str> = line.split('\t').collect();
+            let country_code = CountryCode::ALL
+                .into_iter()
+                .find(|country_code| format!("{:?}", country_code) == fields[0])
+                .unwrap();
+            assert_eq!(fields[2], country_code.calling_code(), "{}", line);
+        }
+    }
+
+    #[test]
+    fn test_numbering_plans() {
+        let singapore = get(CountryCode::SGP).unwrap();
+        assert!(singapore.is_valid("81234567"));
+        assert!(!singapore.is_valid("1234567"));
+        assert!(!singapore.is_valid("123456789012345"));
+
+        let france = get(CountryCode::FRA).unwrap();
+        assert_eq!(france.lengths, vec![9]);
+        assert!(france.is_valid("612345678"));
+        assert!(!france.is_valid("0612345678"));
+        assert_eq!(
+            france.strip_international_prefix("0044207946095"),
+            Some("44207946095")
+        );
+        assert_eq!(france.strip_international_prefix("00044"), None);
+        assert_eq!(france.strip_trunk_prefix("0612345678"), "612345678");
+        assert_eq!(france.strip_trunk_prefix("061234567"), "061234567");
+
+        let format = &france.formats[france.formats.len() - 1];
+        assert!(format.applies_to("6"));
+        assert_eq!(format.national_with_trunk_prefix(), "0$1 $2 $3 $4 $5");
+
+        let italy = get(CountryCode::ITA).unwrap();
+        assert_eq!(italy.strip_trunk_prefix("0612345678"), "0612345678");
+        assert_eq!(france.strip_international_prefix("0612345678"), None);
+
+        assert!(singapore.is_type("mobile", "81234567"));
+        assert!(!singapore.is_type("fixed_line", "81234567"));
+        assert!(singapore.is_type("toll_free", "18001234567"));
+
+        let canada = get(CountryCode::CAN).unwrap();
+        assert!(canada.claims("4165550123"));
+        assert!(!canada.claims("2015550123"));
+        assert!(get(CountryCode::KAZ).unwrap().claims("7012345678"));
+        assert!(!get(CountryCode::KAZ).unwrap().claims("9123456789"));
+    }
+}
@@ -0,0 +1,140 @@
+use crate::phone::metadata;
+use crate::PhoneNumber;
+use derive_more::Display;
+use serde::{Deserialize, Serialize};
+
+/// ### NumberType : What a Phone Number Reaches
+/// The kind of line or service a number belongs to, from the number ranges
+/// of its country. Where a country's mobile and fixed-line ranges overlap,
+/// as in the USA, numbers are `FixedLineOrMobile`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Display)]
+pub enum NumberType {
+    #[display(fmt = "mobile")]
+    Mobile,
+    #[display(fmt = "fixed-line")]
+    FixedLine,
+    #[display(fmt = "fixed-line or mobile")]
+    FixedLineOrMobile,
+    #[display(fmt = "toll-free")]
+    TollFree,
+    #[display(fmt = "premium-rate")]
+    PremiumRate,
+    #[display(fmt = "shared-cost")]
+    SharedCost,
+    #[display(fmt = "VoIP")]
+    Voip,
+    #[display(fmt = "pager")]
+    Pager,
+    /// Universal access numbers, reaching a company from anywhere in the
+    /// country.
+    #[display(fmt = "UAN")]
+    Uan,
+    /// Other numbers, such as personal numbers and voicemail access, and
+    /// numbers of countries the crate has no metadata for.
+    #[display(fmt = "unknown")]
+    Unknown,
+}
+
+/// Number types checked before mobile and fixed-line ranges, with their
+/// names in the metadata.
+const SERVICE_TYPES: [(&str, NumberType); 6] = [
+    ("premium_rate", NumberType::PremiumRate),
+    ("toll_free", NumberType::TollFree),
+    ("shared_cost", NumberType::SharedCost),
+    ("voip", NumberType::Voip),
+    ("pager", NumberType::Pager),
+    ("uan", NumberType::Uan),
+];
+
+impl PhoneNumber {
+    /// Returns the kind of line or service the number belongs to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use custom_type::{CountryCode, NumberType, PhoneNumber};
+    ///
+    /// let mobile = PhoneNumber::parse(CountryCode::INA, "081234567890").unwrap();
+    /// assert_eq!(mobile.number_type(), NumberType::Mobile);
+    ///
+    /// let landline = PhoneNumber::parse(CountryCode::UK, "02079460958").unwrap();
+    /// assert_eq!(landline.number_type(), NumberType::FixedLine);
+    ///
+    /// let toll_free = PhoneNumber::parse(CountryCode::SGP, "18001234567").unwrap();
+    /// assert_eq!(toll_free.number_type(), NumberType::TollFree);
+    /// ```
+    pub fn number_type(&self) -> NumberType {
+        let Some(metadata) = metadata::get(self.country_code) else {
+            return NumberType::Unknown;
+        };
+        let national_number = &self.national_number;
+        if let Some((_, number_type)) = SERVICE_TYPES
+            .iter()
+            .find(|(name, _)| metadata.is_type(name, national_number))
+        {
+            return *number_type;
+        }
+        match (
+            metadata.is_type("fixed_line", national_number),
+            metadata.is_type("mobile", national_number),
+        ) {
+            (true, true) => NumberType::FixedLineOrMobile,
+            (true, false) => NumberType::FixedLine,
+            (false, true) => NumberType::Mobile,
+            (false, false) => NumberType::Unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CountryCode;
+
+    fn number_type(country_code: CountryCode, number: &str) -> NumberType {
+        PhoneNumber::parse(country_code, number)
+            .unwrap()
+            .number_type()
+    }
+
+    #[test]
+    fn test_number_type() {
+        assert_eq!(
+            number_type(CountryCode::SGP, "81234567"),
+            NumberType::Mobile
+        );
+        assert_eq!(
+            number_type(CountryCode::SGP, "61234567"),
+            NumberType::FixedLine
+        );
+        assert_eq!(
+            number_type(CountryCode::SGP, "19001234567"),
+            NumberType::PremiumRate
+        );
+        assert_eq!(number_type(CountryCode::SGP, "31234567"), NumberType::Voip);
+        assert_eq!(
+            number_type(CountryCode::SGP, "70001234567"),
+            NumberType::Uan
+        );
+        assert_eq!(
+            number_type(CountryCode::USA, "2015550123"),
+            NumberType::FixedLineOrMobile
+        );
+        assert_eq!(
+            number_type(CountryCode::USA, "8005550123"),
+            NumberType::TollFree
+        );
+        assert_eq!(
+            number_type(CountryCode::UK, "07400123456"),
+            NumberType::Mobile
+        );
+        assert_eq!(
+            number_type(CountryCode::FRA, "0810123456"),
+            NumberType::SharedCost
+        );
+        assert_eq!(
+            number_type(CountryCode::VAT, "12345678"),
+            NumberType::Unknown
+        );
+    }
+}
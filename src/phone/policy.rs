@@ -0,0 +1,101 @@
+use crate::error::{PhoneError, TypeError};
+use crate::{CountryCode, NumberType, PhoneNumber};
+
+/// ### PhonePolicy : Configurable Phone Number Rules
+/// Chooses how free-form input is read and which kinds of numbers are
+/// accepted. Pass it to [`PhoneNumber::parse_with_policy`].
+///
+/// # Example
+///
+/// ```
+/// use custom_type::{CountryCode, PhoneNumber, PhonePolicy};
+///
+/// let policy = PhonePolicy::new()
+///     .default_region(CountryCode::INA)
+///     .mobile_only();
+///
+/// assert!(PhoneNumber::parse_with_policy(&policy, "0812-3456-7890").is_ok());
+/// assert!(PhoneNumber::parse_with_policy(&policy, "(021) 1234 5678").is_err());
+/// assert!(PhoneNumber::parse_with_policy(&policy, "+65 8123 4567").is_ok());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PhonePolicy {
+    region: Option<CountryCode>,
+    types: Option<Vec<NumberType>>,
+}
+
+impl PhonePolicy {
+    /// Creates a policy that reads numbers in international form and accepts
+    /// every valid number.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads numbers in national form as numbers of `region`, as in
+    /// [`PhoneNumber::parse_with_default_region`].
+    pub fn default_region(mut self, region: CountryCode) -> Self {
+        self.region = Some(region);
+        self
+    }
+
+    /// Accepts only numbers of the given types.
+    pub fn allow_types(mut self, types: impl IntoIterator<Item = NumberType>) -> Self {
+        self.types = Some(types.into_iter().collect());
+        self
+    }
+
+    /// Accepts only numbers that can receive SMS: mobile numbers, and
+    /// numbers of countries where mobile and fixed-line ranges overlap.
+    pub fn mobile_only(self) -> Self {
+        self.allow_types([NumberType::Mobile, NumberType::FixedLineOrMobile])
+    }
+
+    /// Parses `input` and checks the number's type.
+    pub(crate) fn parse(&self, input: &str) -> Result<PhoneNumber, TypeError> {
+        let phone_number = match self.region {
+            Some(region) => PhoneNumber::parse_with_default_region(region, input)?,
+            None => PhoneNumber::parse_international(input)?,
+        };
+        if let Some(types) = &self.types {
+            let number_type = phone_number.number_type();
+            if !types.contains(&number_type) {
+                return Err(PhoneError::DisallowedNumberType { number_type }.into());
+            }
+        }
+        Ok(phone_number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allowed_types() {
+        let policy = PhonePolicy::new().mobile_only();
+        assert!(policy.parse("+44 7400 123456").is_ok());
+        assert!(policy.parse("+1 201-555-0123").is_ok());
+        assert_eq!(
+            policy.parse("+44 20 7946 0958"),
+            Err(TypeError::Phone(PhoneError::DisallowedNumberType {
+                number_type: NumberType::FixedLine
+            }))
+        );
+
+        let policy = PhonePolicy::new()
+            .default_region(CountryCode::USA)
+            .allow_types([NumberType::TollFree]);
+        assert!(policy.parse("(800) 555-0123").is_ok());
+        assert!(policy.parse("(201) 555-0123").is_err());
+    }
+
+    #[test]
+    fn test_default_policy() {
+        let policy = PhonePolicy::new();
+        assert!(policy.parse("+44 20 7946 0958").is_ok());
+        assert_eq!(
+            policy.parse("020 7946 0958"),
+            Err(TypeError::Phone(PhoneError::MissingCountryCode))
+        );
+    }
+}
@@ -1,13 +1,23 @@
-use crate::error::TypeError;
+use crate::error::{TypeError, UrlError};
 use derive_more::Display;
-use regex::Regex;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
 /// ### Url: Parse `impl ToString` into a valid URL
 /// Call the `parse()` method to parse `impl ToString` into a valid URL.
-#[derive(Debug, PartialEq, Display, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Display, Serialize)]
 pub struct Url(String);
 
+impl<'de> Deserialize<'de> for Url {
+    /// Deserializes a string and validates it with [`Url::parse`].
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let url = String::deserialize(deserializer)?;
+        Self::parse(url).map_err(serde::de::Error::custom)
+    }
+}
+
 impl Url {
     /// Parses a string into a valid URL.
     ///
@@ -32,15 +42,28 @@ impl Url {
     /// ```
     pub fn parse(url: impl ToString) -> Result<Self, TypeError> {
         let url = url.to_string();
-        let url_regex = Regex::new(r"^(https?|ftp)://[^\s/$.?#]+\.[^\s]*$").unwrap();
+        validate(&url)?;
+        Ok(Self(url))
+    }
+}
+
+/// Schemes accepted by [`Url::parse`].
+const SCHEMES: [&str; 3] = ["http", "https", "ftp"];
 
-        if url_regex.is_match(&url) {
-            Ok(Self(url))
-        } else {
-            Err(TypeError::ParseError(
-                "unable to parse URL, invalid URL.".to_string(),
-            ))
-        }
+/// Validates `url`, mirroring `^(https?|ftp)://[^\s/$.?#]+\.[^\s]*$`.
+fn validate(url: &str) -> Result<(), UrlError> {
+    let (scheme, rest) = url.split_once("://").ok_or(UrlError::MissingScheme)?;
+    if !SCHEMES.contains(&scheme) {
+        return Err(UrlError::UnsupportedScheme(scheme.to_string()));
+    }
+
+    if let Some(position) = url.chars().position(char::is_whitespace) {
+        return Err(UrlError::InvalidCharacter { position });
+    }
+
+    match rest.find(|c| "/$.?#".contains(c)) {
+        Some(index) if index > 0 && rest[index..].starts_with('.') => Ok(()),
+        _ => Err(UrlError::InvalidHost),
     }
 }
 
@@ -68,21 +91,21 @@ mod tests {
     fn test_invalid_urls() {
         assert_eq!(
             Url::parse("example.com"),
-            Err(TypeError::ParseError(
-                "unable to parse URL, invalid URL.".to_string()
-            ))
+            Err(TypeError::Url(UrlError::MissingScheme))
         );
         assert_eq!(
             Url::parse("http://example"),
-            Err(TypeError::ParseError(
-                "unable to parse URL, invalid URL.".to_string()
-            ))
+            Err(TypeError::Url(UrlError::InvalidHost))
         );
         assert_eq!(
             Url::parse("ftp://example.com/path with spaces"),
-            Err(TypeError::ParseError(
-                "unable to parse URL, invalid URL.".to_string()
-            ))
+            Err(TypeError::Url(UrlError::InvalidCharacter { position: 22 }))
+        );
+        assert_eq!(
+            Url::parse("mailto://example.com"),
+            Err(TypeError::Url(UrlError::UnsupportedScheme(
+                "mailto".to_string()
+            )))
         );
     }
 }